@@ -0,0 +1,322 @@
+use winapi::{ ctypes::c_void, shared::{ minwindef::DWORD, windef::{ HBITMAP__, HDC__, POINT, RECT } }, um::{ wingdi::{ BI_RGB, BITMAPINFO, BITMAPINFOHEADER, BitBlt, CAPTUREBLT, CreateCompatibleBitmap, CreateCompatibleDC, DIB_RGB_COLORS, DeleteDC, DeleteObject, GetDIBits, SelectObject, SRCCOPY }, winuser::{ ClientToScreen, GetClientRect, GetDC, GetWindowLongPtrW, GetWindowRect, GWL_EXSTYLE, PW_RENDERFULLCONTENT, PrintWindow, ReleaseDC, WS_EX_LAYERED } } };
+use std::{ mem, ptr };
+use crate::{ unpremultiply, Rect, WindowController, WindowError, WindowImage };
+
+
+
+pub(crate) const DI_NORMAL:u32 = 0x0003; // Not defined by winapi.
+
+
+
+pub struct CaptureSession {
+	window:WindowController,
+	hdc:*mut HDC__,
+	hbitmap:*mut HBITMAP__,
+	hold:*mut c_void,
+	bitmap_size:[i32; 2],
+	bits:Vec<u8>,
+	include_cursor:bool,
+	preserve_alpha:bool
+}
+impl CaptureSession {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new capture session for the given window. The bitmap is created on the first capture.
+	pub fn new(window:WindowController) -> Result<CaptureSession, WindowError> {
+		unsafe {
+
+			// Create a device context for the window.
+			let dc:*mut HDC__ = GetDC(window.hwnd());
+			if dc.is_null() {
+				return Err(WindowError::ImageCaptureFailed("Could not create device context".to_string()));
+			}
+
+			// Create compatible device context. The common window DC is only needed to create it, so it is released right away.
+			let hdc:*mut HDC__ = CreateCompatibleDC(dc);
+			ReleaseDC(window.hwnd(), dc);
+			if hdc.is_null() {
+				return Err(WindowError::ImageCaptureFailed("Could not create compatible device context.".to_string()))
+			}
+
+			Ok(CaptureSession {
+				window,
+				hdc,
+				hbitmap: ptr::null_mut(),
+				hold: ptr::null_mut(),
+				bitmap_size: [0, 0],
+				bits: Vec::new(),
+				include_cursor: false,
+				preserve_alpha: false
+			})
+		}
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Set whether the mouse cursor should be drawn onto captured images when it is over the captured area.
+	pub fn set_include_cursor(&mut self, include_cursor:bool) -> &mut Self {
+		self.include_cursor = include_cursor;
+		self
+	}
+
+	/// Set whether the alpha channel of layered windows should be kept in captured images, instead of making every pixel opaque. Only applies to captures of the window itself, not to screen captures.
+	pub fn set_preserve_alpha(&mut self, preserve_alpha:bool) -> &mut Self {
+		self.preserve_alpha = preserve_alpha;
+		self
+	}
+
+	/// Tries to create an image of the inner window. Returns a list of u32 0xAARRGGBB values.
+	pub fn capture(&mut self) -> Result<WindowImage, WindowError> {
+		let window_position:[i32; 4] = self.window.position();
+		self.capture_with_bounds([0, 0, window_position[2], window_position[3]])
+	}
+
+	/// Tries to create an image of a subsection of the inner window. Returns a list of u32 0xAARRGGBB values.
+	pub fn capture_with_bounds<T:Into<Rect>>(&mut self, bounds:T) -> Result<WindowImage, WindowError> {
+		let bounds:[i32; 4] = bounds.into().into();
+
+		// Validate bounds width/height.
+		if bounds[2] <= 0 || bounds[3] <= 0 {
+			return Err(WindowError::InvalidBounds("Invalid bounds size".to_string()));
+		}
+
+		// Calculate padded width. Contains the requested bounds inside the client area. Bitmap must be large enough to include left and top padding.
+		let padding:[i32; 4] = self.client_padding();
+		let padded_size:[i32; 2] = [bounds[0] + bounds[2] + padding[0] + padding[2], bounds[1] + bounds[3] + padding[1] + padding[3]];
+		if padded_size[0] <= 0 || padded_size[1] <= 0 {
+			return Err(WindowError::InvalidBounds("Computed padded size is invalid".to_string()));
+		}
+
+		// Make sure a bitmap of the padded size is selected into the DC.
+		self.prepare_bitmap(padded_size)?;
+
+		// Capture image from window to hdc. Renders the full window. Bitmap was sized to include the non-client areas.
+		let result:i32 = unsafe { PrintWindow(self.window.hwnd(), self.hdc, PW_RENDERFULLCONTENT) };
+		if result == 0 {
+			return Err(WindowError::ImageCaptureFailed("PrintWindow failed".to_string()));
+		}
+
+		// Draw the cursor. The bitmap starts at the top-left of the outer window.
+		if self.include_cursor {
+			let window_rect:[i32; 4] = self.window.window_rect();
+			self.draw_cursor([window_rect[0], window_rect[1]]);
+		}
+
+		// Collect data from image, skipping padding. Only layered windows render a meaningful alpha channel.
+		let keep_alpha:bool = self.preserve_alpha && unsafe { GetWindowLongPtrW(self.window.hwnd(), GWL_EXSTYLE) } as u32 & WS_EX_LAYERED != 0;
+		self.read_bitmap([bounds[0] + padding[0], bounds[1] + padding[1], bounds[2], bounds[3]], keep_alpha)
+	}
+
+	/// Tries to create an image of the inner window by copying it from the screen. Works for hardware-accelerated windows that PrintWindow renders black, but includes anything overlapping the window.
+	pub fn capture_screen(&mut self) -> Result<WindowImage, WindowError> {
+		let window_position:[i32; 4] = self.window.position();
+		self.capture_screen_with_bounds([0, 0, window_position[2], window_position[3]])
+	}
+
+	/// Tries to create an image of a subsection of the inner window by copying it from the screen. Works for hardware-accelerated windows that PrintWindow renders black, but includes anything overlapping the window.
+	pub fn capture_screen_with_bounds<T:Into<Rect>>(&mut self, bounds:T) -> Result<WindowImage, WindowError> {
+		let bounds:[i32; 4] = bounds.into().into();
+
+		// Validate bounds width/height.
+		if bounds[2] <= 0 || bounds[3] <= 0 {
+			return Err(WindowError::InvalidBounds("Invalid bounds size".to_string()));
+		}
+
+		// Make sure a bitmap of the bounds size is selected into the DC.
+		self.prepare_bitmap([bounds[2], bounds[3]])?;
+
+		// Copy the area of the screen covered by the bounds to hdc.
+		let client_position:[i32; 4] = self.window.client_rect();
+		unsafe {
+			let screen_dc:*mut HDC__ = GetDC(ptr::null_mut());
+			if screen_dc.is_null() {
+				return Err(WindowError::ImageCaptureFailed("Could not create screen device context".to_string()));
+			}
+			let result:i32 = BitBlt(self.hdc, 0, 0, bounds[2], bounds[3], screen_dc, client_position[0] + bounds[0], client_position[1] + bounds[1], SRCCOPY | CAPTUREBLT);
+			ReleaseDC(ptr::null_mut(), screen_dc);
+			if result == 0 {
+				return Err(WindowError::ImageCaptureFailed("BitBlt failed".to_string()));
+			}
+		}
+
+		// Draw the cursor, which is not part of the screen contents.
+		if self.include_cursor {
+			self.draw_cursor([client_position[0] + bounds[0], client_position[1] + bounds[1]]);
+		}
+
+		// Collect data from image.
+		self.read_bitmap([0, 0, bounds[2], bounds[3]], false)
+	}
+
+
+
+	/* HELPER METHODS */
+
+	/// Get the [left, top, right, bottom] size of the non-client area around the client area of the window.
+	fn client_padding(&self) -> [i32; 4] {
+		unsafe {
+			let mut window_bounds:RECT = mem::zeroed();
+			let mut window_client_bounds:RECT = mem::zeroed();
+			let mut window_topleft:POINT = POINT { x: 0, y: 0 };
+			GetWindowRect(self.window.hwnd(), &mut window_bounds);
+			GetClientRect(self.window.hwnd(), &mut window_client_bounds);
+			ClientToScreen(self.window.hwnd(), &mut window_topleft);
+			[
+				(window_topleft.x - window_bounds.left).max(0),
+				(window_topleft.y - window_bounds.top).max(0),
+				(window_bounds.right - (window_topleft.x + (window_client_bounds.right - window_client_bounds.left))).max(0),
+				(window_bounds.bottom - (window_topleft.y + (window_client_bounds.bottom - window_client_bounds.top))).max(0)
+			]
+		}
+	}
+
+	/// Draw the mouse cursor onto the current bitmap, if it is visible. The origin is the screen position of the top-left of the bitmap.
+	fn draw_cursor(&self, origin:[i32; 2]) {
+		use winapi::um::winuser::{ DrawIconEx, GetCursorInfo, GetIconInfo, CURSORINFO, CURSOR_SHOWING, ICONINFO };
+
+		unsafe {
+			let mut cursor_info:CURSORINFO = mem::zeroed();
+			cursor_info.cbSize = mem::size_of::<CURSORINFO>() as DWORD;
+			if GetCursorInfo(&mut cursor_info) == 0 || cursor_info.flags & CURSOR_SHOWING == 0 || cursor_info.hCursor.is_null() {
+				return;
+			}
+
+			// Find the cursor hotspot, the point of the cursor image that is at the cursor position.
+			let mut icon_info:ICONINFO = mem::zeroed();
+			if GetIconInfo(cursor_info.hCursor, &mut icon_info) == 0 {
+				return;
+			}
+			if !icon_info.hbmMask.is_null() {
+				DeleteObject(icon_info.hbmMask as *mut _);
+			}
+			if !icon_info.hbmColor.is_null() {
+				DeleteObject(icon_info.hbmColor as *mut _);
+			}
+
+			// Skip if the cursor is not over the captured area.
+			let position:[i32; 2] = [cursor_info.ptScreenPos.x - origin[0], cursor_info.ptScreenPos.y - origin[1]];
+			if position[0] < 0 || position[1] < 0 || position[0] >= self.bitmap_size[0] || position[1] >= self.bitmap_size[1] {
+				return;
+			}
+			DrawIconEx(self.hdc, position[0] - icon_info.xHotspot as i32, position[1] - icon_info.yHotspot as i32, cursor_info.hCursor, 0, 0, 0, ptr::null_mut(), DI_NORMAL);
+		}
+	}
+
+	/// Read the given [x, y, w, h] area of the current bitmap into an image. Unless the alpha channel is kept, all pixels are made opaque.
+	fn read_bitmap(&mut self, area:[i32; 4], keep_alpha:bool) -> Result<WindowImage, WindowError> {
+		unsafe {
+
+			// Prepare BITMAPINFO for the bitmap size (top-down)
+			let mut bitmap_info:BITMAPINFO = mem::zeroed();
+			bitmap_info.bmiHeader.biSize = mem::size_of::<BITMAPINFOHEADER>() as DWORD;
+			bitmap_info.bmiHeader.biWidth = self.bitmap_size[0];
+			bitmap_info.bmiHeader.biHeight = -self.bitmap_size[1]; // Negative to get data top-down.
+			bitmap_info.bmiHeader.biPlanes = 1;
+			bitmap_info.bmiHeader.biBitCount = 32;
+			bitmap_info.bmiHeader.biCompression = BI_RGB;
+
+			// Read the full capture (BGRA) into the reused buffer.
+			let res:i32 = GetDIBits(self.hdc, self.hbitmap, 0, self.bitmap_size[1] as u32, self.bits.as_mut_ptr() as *mut c_void, &mut bitmap_info, DIB_RGB_COLORS);
+			if res == 0 {
+				return Err(WindowError::ImageCaptureFailed("GetDIBits failed.".to_string()));
+			}
+		}
+
+		// Collect data from the requested area.
+		let bits:&[u8] = &self.bits;
+		let mut pixels:Vec<u32> = vec![0x00000000; (area[2] * area[3]) as usize];
+		for output_y in 0..area[3] {
+			for output_x in 0..area[2] {
+				let (input_x, input_y) = (area[0] + output_x, area[1] + output_y);
+				let output_index:usize = (output_y * area[2] + output_x) as usize;
+				let input_index:usize = (input_y * self.bitmap_size[0] + input_x) as usize;
+				if keep_alpha {
+					pixels[output_index] = unpremultiply(u32::from_be_bytes([bits[input_index * 4 + 3], bits[input_index * 4 + 2], bits[input_index * 4 + 1], bits[input_index * 4]]));
+				} else {
+					pixels[output_index] = u32::from_be_bytes([0xFF, bits[input_index * 4 + 2], bits[input_index * 4 + 1], bits[input_index * 4]]);
+				}
+			}
+		}
+
+		// GDI-rendered content of windows made translucent through SetLayeredWindowAttributes comes back without alpha, treat it as opaque.
+		if keep_alpha && pixels.iter().all(|pixel| pixel >> 24 == 0) {
+			pixels.iter_mut().for_each(|pixel| *pixel |= 0xFF000000);
+		}
+
+		// Return image.
+		Ok(WindowImage {
+			data: pixels,
+			width: area[2] as usize,
+			height: area[3] as usize
+		})
+	}
+
+	/// Make sure a bitmap of the given size is selected into the compatible DC. Only recreates the bitmap when the size changed.
+	fn prepare_bitmap(&mut self, size:[i32; 2]) -> Result<(), WindowError> {
+		if !self.hbitmap.is_null() && self.bitmap_size == size {
+			return Ok(());
+		}
+		self.release_bitmap();
+
+		unsafe {
+
+			// Create a compatible bitmap sized to the padded size. Uses a short-lived window DC, as the bitmap has to match the window and not the monochrome compatible DC.
+			let dc:*mut HDC__ = GetDC(self.window.hwnd());
+			if dc.is_null() {
+				return Err(WindowError::ImageCaptureFailed("Could not create device context".to_string()));
+			}
+			let hbitmap:*mut HBITMAP__ = CreateCompatibleBitmap(dc, size[0], size[1]);
+			ReleaseDC(self.window.hwnd(), dc);
+			if hbitmap.is_null() {
+				return Err(WindowError::ImageCaptureFailed("Could not create compatible bitmap.".to_string()))
+			}
+
+			// Select the bitmap into the DC.
+			let hold:*mut c_void = SelectObject(self.hdc, hbitmap as *mut _);
+			if hold.is_null() {
+				DeleteObject(hbitmap as *mut _);
+				return Err(WindowError::ImageCaptureFailed("Could not select the bitmap in the device context.".to_string()))
+			}
+
+			self.hbitmap = hbitmap;
+			self.hold = hold;
+			self.bitmap_size = size;
+			self.bits = vec![0u8; (size[0] * size[1] * 4) as usize];
+		}
+		Ok(())
+	}
+
+	/// Deselect and delete the current bitmap, if any.
+	fn release_bitmap(&mut self) {
+		if !self.hbitmap.is_null() {
+			unsafe {
+				SelectObject(self.hdc, self.hold);
+				DeleteObject(self.hbitmap as *mut _);
+			}
+			self.hbitmap = ptr::null_mut();
+			self.hold = ptr::null_mut();
+			self.bitmap_size = [0, 0];
+		}
+	}
+}
+impl Drop for CaptureSession {
+	fn drop(&mut self) {
+		self.release_bitmap();
+		unsafe {
+			DeleteDC(self.hdc);
+		}
+	}
+}
+
+
+
+impl WindowController {
+
+	/// Create a capture session that reuses its device contexts and bitmap between captures.
+	pub fn capture_session(&self) -> Result<CaptureSession, WindowError> {
+		CaptureSession::new(self.clone())
+	}
+}
@@ -1,7 +1,25 @@
 mod window_controller;
 mod window_style;
 mod window_image;
+mod monitor;
+mod window_input;
+mod capture_session;
+mod window_dwm;
+mod window_error;
+mod window_event;
+mod window_placement;
+mod rect;
+mod window_query;
+mod window_icon;
 
 pub use window_controller::*;
 pub use window_style::*;
-pub use window_image::*;
\ No newline at end of file
+pub use window_image::*;
+pub use monitor::*;
+pub use capture_session::*;
+pub use window_dwm::*;
+pub use window_error::*;
+pub use window_event::*;
+pub use window_placement::*;
+pub use rect::*;
+pub use window_query::*;
\ No newline at end of file
@@ -0,0 +1,128 @@
+use winapi::{ shared::{ minwindef::{ BOOL, DWORD, LPARAM, TRUE }, windef::{ HDC, HMONITOR, LPRECT, RECT } }, um::winuser::{ EnumDisplayMonitors, GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITORINFOF_PRIMARY, MONITOR_DEFAULTTONEAREST } };
+use std::{ mem, ptr };
+use crate::WindowController;
+
+
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Monitor {
+	pub handle:HMONITOR,
+	pub bounds:[i32; 4], // xywh
+	pub work_area:[i32; 4], // xywh, excludes the taskbar.
+	pub is_primary:bool
+}
+impl Monitor {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Get a monitor from a specific handle.
+	fn from_handle(handle:HMONITOR) -> Monitor {
+		unsafe {
+			let mut monitor_info:MONITORINFO = mem::zeroed();
+			monitor_info.cbSize = mem::size_of::<MONITORINFO>() as DWORD;
+			GetMonitorInfoW(handle, &mut monitor_info);
+			Monitor {
+				handle,
+				bounds: Monitor::rect_to_xywh(&monitor_info.rcMonitor),
+				work_area: Monitor::rect_to_xywh(&monitor_info.rcWork),
+				is_primary: monitor_info.dwFlags & MONITORINFOF_PRIMARY != 0
+			}
+		}
+	}
+
+	/// Get all monitors connected to the system.
+	pub fn all() -> Vec<Monitor> {
+		let mut monitors:Vec<Monitor> = Vec::new();
+		unsafe { EnumDisplayMonitors(ptr::null_mut(), ptr::null(), Some(Monitor::externally_get_monitors), &mut monitors as *mut Vec<Monitor> as LPARAM); }
+		monitors
+	}
+	unsafe extern "system" fn externally_get_monitors(handle:HMONITOR, _device_context:HDC, _bounds:LPRECT, control_handle:LPARAM) -> BOOL {
+		unsafe {
+			let monitors:&mut Vec<Monitor> = &mut *(control_handle as *mut Vec<Monitor>);
+			monitors.push(Monitor::from_handle(handle));
+			TRUE
+		}
+	}
+
+
+
+	/* HELPER METHODS */
+
+	/// Convert a RECT to an xywh array.
+	fn rect_to_xywh(rect:&RECT) -> [i32; 4] {
+		[rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top]
+	}
+}
+
+
+
+impl WindowController {
+
+	/// Get the monitor this window is on. Falls back to the nearest monitor if the window is not on any.
+	pub fn monitor(&self) -> Monitor {
+		Monitor::from_handle(unsafe { MonitorFromWindow(self.hwnd(), MONITOR_DEFAULTTONEAREST) })
+	}
+
+	/// Move the window to another monitor, keeping its size and its offset within the work area. The window is kept within the target work area where possible. Maximized windows are maximized again on the new monitor.
+	pub fn move_to_monitor(&self, monitor:&Monitor) {
+		let was_maximized:bool = self.is_maximized();
+		if was_maximized {
+			self.restore();
+		}
+
+		let current_work_area:[i32; 4] = self.monitor().work_area;
+		let target_work_area:[i32; 4] = monitor.work_area;
+		let window_rect:[i32; 4] = self.window_rect();
+		let offset:[i32; 2] = [
+			(window_rect[0] - current_work_area[0]).min(target_work_area[2] - window_rect[2]).max(0),
+			(window_rect[1] - current_work_area[1]).min(target_work_area[3] - window_rect[3]).max(0)
+		];
+		self.move_to(target_work_area[0] + offset[0], target_work_area[1] + offset[1]);
+
+		if was_maximized {
+			self.maximize();
+		}
+	}
+
+	/// Snap the window to the left half of the work area of its monitor, like Win+Left.
+	pub fn snap_left(&self) {
+		let work_area:[i32; 4] = self.monitor().work_area;
+		self.snap_to([work_area[0], work_area[1], work_area[2] / 2, work_area[3]]);
+	}
+
+	/// Snap the window to the right half of the work area of its monitor, like Win+Right.
+	pub fn snap_right(&self) {
+		let work_area:[i32; 4] = self.monitor().work_area;
+		let half_width:i32 = work_area[2] / 2;
+		self.snap_to([work_area[0] + half_width, work_area[1], work_area[2] - half_width, work_area[3]]);
+	}
+
+	/// Snap the window to the top half of the work area of its monitor.
+	pub fn snap_top(&self) {
+		let work_area:[i32; 4] = self.monitor().work_area;
+		self.snap_to([work_area[0], work_area[1], work_area[2], work_area[3] / 2]);
+	}
+
+	/// Snap the window to the bottom half of the work area of its monitor.
+	pub fn snap_bottom(&self) {
+		let work_area:[i32; 4] = self.monitor().work_area;
+		let half_height:i32 = work_area[3] / 2;
+		self.snap_to([work_area[0], work_area[1] + half_height, work_area[2], work_area[3] - half_height]);
+	}
+
+	/// Place the visible frame of the window exactly on the given [x, y, w, h] area.
+	fn snap_to(&self, area:[i32; 4]) {
+		if self.is_maximized() || self.is_minimized() {
+			self.restore();
+		}
+
+		// The window rect includes invisible resize borders, grow the target area by them so the visible frame sits flush.
+		let window_rect:[i32; 4] = self.window_rect();
+		let frame_rect:[i32; 4] = self.dwm_frame_rect().unwrap_or(window_rect);
+		let left_border:i32 = frame_rect[0] - window_rect[0];
+		let top_border:i32 = frame_rect[1] - window_rect[1];
+		let right_border:i32 = (window_rect[0] + window_rect[2]) - (frame_rect[0] + frame_rect[2]);
+		let bottom_border:i32 = (window_rect[1] + window_rect[3]) - (frame_rect[1] + frame_rect[3]);
+		self.set_pos([area[0] - left_border, area[1] - top_border, area[2] + left_border + right_border, area[3] + top_border + bottom_border]);
+	}
+}
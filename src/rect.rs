@@ -0,0 +1,24 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rect {
+	pub x:i32,
+	pub y:i32,
+	pub width:i32,
+	pub height:i32
+}
+impl Rect {
+
+	/// Create a new rect.
+	pub fn new(x:i32, y:i32, width:i32, height:i32) -> Rect {
+		Rect { x, y, width, height }
+	}
+}
+impl From<[i32; 4]> for Rect {
+	fn from(xywh:[i32; 4]) -> Rect {
+		Rect::new(xywh[0], xywh[1], xywh[2], xywh[3])
+	}
+}
+impl From<Rect> for [i32; 4] {
+	fn from(rect:Rect) -> [i32; 4] {
+		[rect.x, rect.y, rect.width, rect.height]
+	}
+}
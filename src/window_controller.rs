@@ -1,18 +1,63 @@
-use winapi::{ shared::windef::HWND, um::winuser::GetForegroundWindow };
-use std::{ error::Error, sync::{ Mutex, MutexGuard } };
-use crate::WindowStyle;
+use winapi::{ shared::{ minwindef::HRGN, windef::HWND }, um::winuser::GetForegroundWindow };
+use std::{ collections::HashMap, sync::{ LazyLock, Mutex, MutexGuard }, thread::{ self, sleep, JoinHandle }, time::{ Duration, Instant } };
+use crate::{ Rect, WindowError, WindowPlacement, WindowStyle };
 
 
 
-static WINDOW_COLLECTOR_LOCK:Mutex<()> = Mutex::new(());
-static mut WINDOW_FILTER:Option<Box<dyn Fn(&WindowController) -> bool>> = None;
-static mut STOP_AFTER_FIRST:bool = false;
-static mut FOUND_WINDOWS:Vec<WindowController> = Vec::new();
+const WAIT_POLL_INTERVAL:Duration = Duration::from_millis(50);
+const FADE_STEP_INTERVAL:Duration = Duration::from_millis(16);
+const MAX_EXE_PATH_LENGTH:usize = 32768; // Maximum length of an extended-length path.
+const RESPONSE_TIMEOUT_MS:u32 = 1000;
+const WDA_EXCLUDEFROMCAPTURE:u32 = 0x00000011; // Not defined by winapi.
+const SHELL_MIN_ALL:usize = 419; // Taskbar command, not defined by winapi.
+const SHELL_MIN_ALL_UNDO:usize = 416; // Taskbar command, not defined by winapi.
+#[cfg(target_pointer_width = "64")]
+const PEB_PROCESS_PARAMETERS_OFFSET:usize = 0x20;
+#[cfg(target_pointer_width = "32")]
+const PEB_PROCESS_PARAMETERS_OFFSET:usize = 0x10;
+#[cfg(target_pointer_width = "64")]
+const PROCESS_PARAMETERS_COMMAND_LINE_OFFSET:usize = 0x70;
+#[cfg(target_pointer_width = "32")]
+const PROCESS_PARAMETERS_COMMAND_LINE_OFFSET:usize = 0x40;
+static FULLSCREEN_STATES:LazyLock<Mutex<HashMap<usize, FullscreenState>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
 
 
 
-#[derive(Clone, PartialEq)]
+struct WindowCollector<'a> {
+	filter:&'a dyn Fn(&WindowController) -> bool,
+	limit:usize,
+	found_windows:Vec<WindowController>
+}
+
+// Matches PROCESS_BASIC_INFORMATION, not defined by winapi.
+#[repr(C)]
+struct ProcessBasicInformation {
+	exit_status:isize,
+	peb_base_address:usize,
+	affinity_mask:usize,
+	base_priority:isize,
+	unique_process_id:usize,
+	inherited_from_unique_process_id:usize
+}
+
+#[link(name = "ntdll")]
+unsafe extern "system" {
+	fn NtQueryInformationProcess(process_handle:winapi::um::winnt::HANDLE, information_class:u32, information:*mut winapi::ctypes::c_void, information_length:u32, return_length:*mut u32) -> i32;
+}
+
+struct FullscreenState {
+	style_flags:isize,
+	extended_style_flags:isize,
+	placement:WindowPlacement
+}
+
+
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct WindowController(HWND);
+// HWNDs are process-wide handles rather than thread-bound resources, all calls made through the controller are safe from any thread.
+unsafe impl Send for WindowController {}
+unsafe impl Sync for WindowController {}
 #[allow(dead_code)]
 impl WindowController {
 
@@ -28,15 +73,64 @@ impl WindowController {
 		WindowController(hwnd)
 	}
 
+	/// Get a controller to the desktop window, which covers the entire screen.
+	pub fn desktop() -> WindowController {
+		WindowController(unsafe { winapi::um::winuser::GetDesktopWindow() })
+	}
+
+	/// Get a controller to the shell window, usually the window drawing the desktop icons. Returns None if no shell is running.
+	pub fn shell_window() -> Option<WindowController> {
+		let hwnd:HWND = unsafe { winapi::um::winuser::GetShellWindow() };
+		if hwnd.is_null() {
+			None
+		} else {
+			Some(WindowController(hwnd))
+		}
+	}
+
+	/// Get a controller to the window at the given screen position.
+	pub fn from_point(x:i32, y:i32) -> Option<WindowController> {
+		use winapi::shared::windef::POINT;
+
+		let hwnd:HWND = unsafe { winapi::um::winuser::WindowFromPoint(POINT { x, y }) };
+		if hwnd.is_null() {
+			None
+		} else {
+			Some(WindowController(hwnd))
+		}
+	}
+
 	/// Find window by its title.
 	pub fn find_by_title(title:&str) -> Option<WindowController> {
 		let title:String = title.to_string();
 		WindowController::find_one(move |window| window.title().contains(&title))
 	}
 
+	/// Find window by its class name.
+	pub fn find_by_class(class:&str) -> Option<WindowController> {
+		let class:String = class.to_string();
+		WindowController::find_one(move |window| window.class_name() == class)
+	}
+
+	/// Find all windows belonging to the process with the given ID.
+	pub fn find_by_pid(pid:u32) -> Vec<WindowController> {
+		WindowController::find_all(move |window| window.pid() == pid)
+	}
+
+	/// Find all windows of which the executable name matches the given name, case-insensitive.
+	pub fn find_by_process_name(name:&str) -> Vec<WindowController> {
+		let name:String = name.to_lowercase();
+		WindowController::find_all(move |window| window.process_name().map(|process_name| process_name.to_lowercase() == name).unwrap_or(false))
+	}
+
+	/// Find all windows that would show up in Alt-Tab: visible, not cloaked, not a tool window and either unowned or explicitly marked as app window.
+	pub fn find_visible_app_windows() -> Vec<WindowController> {
+		WindowController::find_all(|window| window.is_app_window())
+	}
+
 	/// Try to find one window matching the given filter.
 	pub fn find_one<T:Fn(&WindowController) -> bool + 'static>(filter:T) -> Option<WindowController> {
-		let found:Vec<WindowController> = WindowController::find(filter, true);
+		let found:Vec<WindowController> = WindowController::find(&filter, 1);
 		if found.is_empty() {
 			None
 		} else {
@@ -46,30 +140,42 @@ impl WindowController {
 
 	/// Find all windows matching the given filter.
 	pub fn find_all<T:Fn(&WindowController) -> bool + 'static>(filter:T) -> Vec<WindowController> {
-		WindowController::find(filter, false)
+		WindowController::find(&filter, usize::MAX)
+	}
+
+	/// Find at most the given amount of windows matching the given filter. Stops searching once the limit is reached.
+	pub fn find_n<T:Fn(&WindowController) -> bool + 'static>(filter:T, limit:usize) -> Vec<WindowController> {
+		if limit == 0 {
+			return Vec::new();
+		}
+		WindowController::find(&filter, limit)
+	}
+
+	/// Iterate over all top-level windows, in z-order.
+	pub fn iter() -> impl Iterator<Item = WindowController> {
+		WindowController::find(&|_: &WindowController| true, usize::MAX).into_iter()
 	}
 
 	/// Get a controller to all existing windows.
-	#[allow(static_mut_refs)]
-	fn find<T:Fn(&WindowController) -> bool + 'static>(filter:T, find_one:bool) -> Vec<WindowController> {
-		unsafe {
-			let collect_lock:MutexGuard<'_, ()> = WINDOW_COLLECTOR_LOCK.lock().unwrap();
-			WINDOW_FILTER = Some(Box::new(filter));
-			STOP_AFTER_FIRST = find_one;
-			FOUND_WINDOWS = Vec::new();
-			winapi::um::winuser::EnumWindows(Some(WindowController::externally_get_window_controllers), 0);
-			let windows:Vec<WindowController> = FOUND_WINDOWS.clone();
-			drop(collect_lock);
-			windows
-		}
-	}
-	#[allow(static_mut_refs)]
-	unsafe extern "system" fn externally_get_window_controllers(hwnd:HWND, _control_handle:winapi::shared::minwindef::LPARAM) -> winapi::shared::minwindef::BOOL  {
+	fn find<T:Fn(&WindowController) -> bool>(filter:&T, limit:usize) -> Vec<WindowController> {
+		use winapi::shared::minwindef::LPARAM;
+
+		// The collector is passed to the callback through the LPARAM, which keeps enumeration reentrant.
+		let mut collector:WindowCollector = WindowCollector {
+			filter,
+			limit,
+			found_windows: Vec::new()
+		};
+		unsafe { winapi::um::winuser::EnumWindows(Some(WindowController::externally_get_window_controllers), &mut collector as *mut WindowCollector as LPARAM); }
+		collector.found_windows
+	}
+	unsafe extern "system" fn externally_get_window_controllers(hwnd:HWND, control_handle:winapi::shared::minwindef::LPARAM) -> winapi::shared::minwindef::BOOL  {
 		unsafe {
+			let collector:&mut WindowCollector = &mut *(control_handle as *mut WindowCollector);
 			let controller:WindowController = WindowController(hwnd);
-			if (WINDOW_FILTER.as_ref().unwrap())(&controller) {
-				FOUND_WINDOWS.push(controller);
-				if STOP_AFTER_FIRST {
+			if (collector.filter)(&controller) {
+				collector.found_windows.push(controller);
+				if collector.found_windows.len() >= collector.limit {
 					return winapi::shared::minwindef::FALSE;
 				}
 			}
@@ -81,17 +187,39 @@ impl WindowController {
 
 	/* ACTION METHODS */
 
-	/// Set this window as the active one.
-	pub fn activate(&self) {
-		use winapi::um::winuser::{ SetForegroundWindow, SetWindowPos, HWND_TOP, SWP_SHOWWINDOW, SW_SHOW};
+	/// Set this window as the active one. Returns whether the window is active afterwards.
+	pub fn activate(&self) -> bool {
+		use winapi::um::{ processthreadsapi::GetCurrentThreadId, winuser::{ AttachThreadInput, BringWindowToTop, SetForegroundWindow, SetWindowPos, keybd_event, HWND_TOP, KEYEVENTF_KEYUP, SWP_NOMOVE, SWP_NOSIZE, SWP_SHOWWINDOW, VK_MENU } };
 
-		if !self.is_active() {
-			unsafe {
-				let pos:[i32; 4] = self.position();
-				SetWindowPos(self.0, HWND_TOP, pos[0], pos[1], pos[0] + pos[2], pos[1] + pos[3], SWP_SHOWWINDOW | SW_SHOW as u32);
+		if self.is_active() {
+			return true;
+		}
+		unsafe {
+
+			// Attach the input of this thread to the foreground and target threads, so the foreground lock allows switching.
+			let current_thread:u32 = GetCurrentThreadId();
+			let mut attached_threads:Vec<u32> = vec![WindowController::active().thread_id(), self.thread_id()];
+			attached_threads.dedup();
+			attached_threads.retain(|thread| *thread != 0 && *thread != current_thread && AttachThreadInput(current_thread, *thread, 1) != 0);
+
+			// Bring the window to the front.
+			SetWindowPos(self.0, HWND_TOP, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_SHOWWINDOW);
+			BringWindowToTop(self.0);
+			SetForegroundWindow(self.0);
+
+			// If the foreground lock still blocks activation, a tap on the ALT key releases it.
+			if !self.is_active() {
+				keybd_event(VK_MENU as u8, 0, 0, 0);
+				keybd_event(VK_MENU as u8, 0, KEYEVENTF_KEYUP, 0);
 				SetForegroundWindow(self.0);
 			}
+
+			// Detach input again.
+			for thread in attached_threads {
+				AttachThreadInput(current_thread, thread, 0);
+			}
 		}
+		self.is_active()
 	}
 	
 	/// Minimize the window.
@@ -99,22 +227,323 @@ impl WindowController {
 		unsafe { winapi::um::winuser::ShowWindow(self.0, winapi::um::winuser::SW_MINIMIZE); }
 	}
 	
-	/// Post a message to the window.
-	pub fn post_message(&self, message:u32) {
-		unsafe { winapi::um::winuser::PostMessageW(self.0, message, 0, 0); };
+	/// Maximize the window.
+	pub fn maximize(&self) {
+		unsafe { winapi::um::winuser::ShowWindow(self.0, winapi::um::winuser::SW_MAXIMIZE); }
+	}
+	
+	/// Restore a minimized or maximized window to its normal placement.
+	pub fn restore(&self) {
+		unsafe { winapi::um::winuser::ShowWindow(self.0, winapi::um::winuser::SW_RESTORE); }
+	}
+	
+	/// Hide the window.
+	pub fn hide(&self) {
+		unsafe { winapi::um::winuser::ShowWindow(self.0, winapi::um::winuser::SW_HIDE); }
+	}
+	
+	/// Show the window.
+	pub fn show(&self) {
+		unsafe { winapi::um::winuser::ShowWindow(self.0, winapi::um::winuser::SW_SHOW); }
+	}
+	
+	/// Post a message to the window without waiting for it to be processed.
+	pub fn post_message(&self, message:u32, wparam:usize, lparam:isize) {
+		unsafe { winapi::um::winuser::PostMessageW(self.0, message, wparam, lparam); };
+	}
+
+	/// Send a message to the window and wait for it to be processed. Returns the result of the message.
+	pub fn send_message(&self, message:u32, wparam:usize, lparam:isize) -> isize {
+		unsafe { winapi::um::winuser::SendMessageW(self.0, message, wparam, lparam) }
+	}
+
+	/// Set the title of the window.
+	pub fn set_title(&self, title:&str) {
+		let title_wide:Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+		unsafe { winapi::um::winuser::SetWindowTextW(self.0, title_wide.as_ptr()); }
+	}
+
+	/// Enable or disable mouse and keyboard input to the window.
+	pub fn set_enabled(&self, enabled:bool) {
+		unsafe { winapi::um::winuser::EnableWindow(self.0, enabled as winapi::shared::minwindef::BOOL); }
+	}
+
+	/// Store user data on the window. Note that this overwrites any value the application owning the window stored there itself.
+	pub fn set_user_data(&self, value:isize) {
+		unsafe { winapi::um::winuser::SetWindowLongPtrW(self.0, winapi::um::winuser::GWLP_USERDATA, value); }
+	}
+
+	/// Set the owner of the window, or clear it with None. Only applies to top-level windows.
+	pub fn set_owner(&self, owner:Option<&WindowController>) {
+		use winapi::um::winuser::{ SetWindowLongPtrW, GWLP_HWNDPARENT };
+
+		let owner_hwnd:isize = owner.map(|owner| owner.hwnd() as isize).unwrap_or(0);
+		unsafe { SetWindowLongPtrW(self.0, GWLP_HWNDPARENT, owner_hwnd); }
 	}
 
 	/// Move the window to a new xywh position.
-	pub fn set_pos(&self, position:[i32; 4]) {
+	pub fn set_pos<T:Into<Rect>>(&self, position:T) {
 		use winapi::um::winuser::{ SetWindowPos, HWND_TOP, SWP_NOZORDER };
+
+		let position:[i32; 4] = position.into().into();
 		unsafe { SetWindowPos(self.0, HWND_TOP, position[0], position[1], position[2], position[3], SWP_NOZORDER); }
 	}
+
+	/// Move the window to a new xy position, keeping its current size.
+	pub fn move_to(&self, x:i32, y:i32) {
+		use winapi::um::winuser::{ SetWindowPos, HWND_TOP, SWP_NOSIZE, SWP_NOZORDER };
+		unsafe { SetWindowPos(self.0, HWND_TOP, x, y, 0, 0, SWP_NOSIZE | SWP_NOZORDER); }
+	}
+
+	/// Resize the window, keeping its current position. Sizes below 1 are clamped to 1.
+	pub fn resize(&self, width:i32, height:i32) {
+		use winapi::um::winuser::{ SetWindowPos, HWND_TOP, SWP_NOMOVE, SWP_NOZORDER };
+		unsafe { SetWindowPos(self.0, HWND_TOP, 0, 0, width.max(1), height.max(1), SWP_NOMOVE | SWP_NOZORDER); }
+	}
+
+	/// Center the window within the work area of the monitor it is on, keeping its current size.
+	pub fn center_on_screen(&self) {
+		let work_area:[i32; 4] = self.monitor().work_area;
+		let window_rect:[i32; 4] = self.window_rect();
+		self.move_to(work_area[0] + (work_area[2] - window_rect[2]) / 2, work_area[1] + (work_area[3] - window_rect[3]) / 2);
+	}
 	
+	/// Move the window to the bottom of the z-order without activating it.
+	pub fn send_to_bottom(&self) {
+		use winapi::um::winuser::{ SetWindowPos, HWND_BOTTOM, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE };
+		unsafe { SetWindowPos(self.0, HWND_BOTTOM, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE); }
+	}
+
+	/// Place the window directly below the given window in the z-order without activating it.
+	pub fn set_z_order_after(&self, other:&WindowController) {
+		use winapi::um::winuser::{ SetWindowPos, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE };
+		unsafe { SetWindowPos(self.0, other.hwnd(), 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE); }
+	}
+
+	/// Place the window above all non-topmost windows and keep it there, without activating it.
+	pub fn set_z_order_topmost(&self) {
+		use winapi::um::winuser::{ SetWindowPos, HWND_TOPMOST, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE };
+		unsafe { SetWindowPos(self.0, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE); }
+	}
+
+	/// Place the window above all non-topmost windows, but behind all topmost windows, without activating it.
+	pub fn set_z_order_no_topmost(&self) {
+		use winapi::um::winuser::{ SetWindowPos, HWND_NOTOPMOST, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE };
+		unsafe { SetWindowPos(self.0, HWND_NOTOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE); }
+	}
+
+	/// Flash the window caption and taskbar button to request attention. A count of 0 keeps flashing until the window comes to the foreground.
+	pub fn flash(&self, count:u32) {
+		use winapi::um::winuser::{ FlashWindowEx, FLASHWINFO, FLASHW_ALL, FLASHW_TIMERNOFG };
+		use std::mem;
+
+		let mut flash_info:FLASHWINFO = FLASHWINFO { cbSize: mem::size_of::<FLASHWINFO>() as u32, hwnd: self.0, dwFlags: FLASHW_ALL | FLASHW_TIMERNOFG, uCount: count, dwTimeout: 0 };
+		unsafe { FlashWindowEx(&mut flash_info); }
+	}
+
+	/// Stop flashing the window.
+	pub fn stop_flash(&self) {
+		use winapi::um::winuser::{ FlashWindowEx, FLASHWINFO, FLASHW_STOP };
+		use std::mem;
+
+		let mut flash_info:FLASHWINFO = FLASHWINFO { cbSize: mem::size_of::<FLASHWINFO>() as u32, hwnd: self.0, dwFlags: FLASHW_STOP, uCount: 0, dwTimeout: 0 };
+		unsafe { FlashWindowEx(&mut flash_info); }
+	}
+	
+	/// Toggle borderless fullscreen. When enabling, the current styles and placement are stored and the window is stretched over its monitor. When disabling, the stored styles and placement are restored.
+	pub fn set_fullscreen(&self, fullscreen:bool) {
+		use winapi::um::winuser::{ GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, GWL_STYLE, HWND_TOP, SWP_FRAMECHANGED, SWP_NOMOVE, SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOZORDER, WS_CAPTION, WS_MAXIMIZEBOX, WS_MINIMIZEBOX, WS_SYSMENU, WS_THICKFRAME };
+		use std::ptr;
+
+		// Forget the states of closed windows, their handles may have been reused by new windows.
+		let mut fullscreen_states:MutexGuard<'_, HashMap<usize, FullscreenState>> = FULLSCREEN_STATES.lock().unwrap();
+		fullscreen_states.retain(|hwnd, _| WindowController(*hwnd as HWND).exists());
+		unsafe {
+			if fullscreen {
+				if fullscreen_states.contains_key(&(self.0 as usize)) {
+					return;
+				}
+
+				// Store the current state.
+				let state:FullscreenState = FullscreenState {
+					style_flags: GetWindowLongPtrW(self.0, GWL_STYLE),
+					extended_style_flags: GetWindowLongPtrW(self.0, GWL_EXSTYLE),
+					placement: self.placement()
+				};
+
+				// Strip the borders and cover the monitor.
+				let monitor_bounds:[i32; 4] = self.monitor().bounds;
+				SetWindowLongPtrW(self.0, GWL_STYLE, state.style_flags & !((WS_CAPTION | WS_THICKFRAME | WS_MINIMIZEBOX | WS_MAXIMIZEBOX | WS_SYSMENU) as isize));
+				SetWindowPos(self.0, HWND_TOP, monitor_bounds[0], monitor_bounds[1], monitor_bounds[2], monitor_bounds[3], SWP_FRAMECHANGED | SWP_NOOWNERZORDER);
+				fullscreen_states.insert(self.0 as usize, state);
+			} else if let Some(state) = fullscreen_states.remove(&(self.0 as usize)) {
+
+				// Restore the stored state.
+				SetWindowLongPtrW(self.0, GWL_STYLE, state.style_flags);
+				SetWindowLongPtrW(self.0, GWL_EXSTYLE, state.extended_style_flags);
+				self.set_placement(&state.placement);
+				SetWindowPos(self.0, ptr::null_mut(), 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_FRAMECHANGED);
+			}
+		}
+	}
+	
+	/// Toggle excluding the window from screen captures. Excluded windows remain visible to the user, but show up black or not at all in screenshots and recordings.
+	pub fn set_capture_exclude(&self, exclude:bool) -> Result<(), WindowError> {
+		use winapi::um::winuser::{ SetWindowDisplayAffinity, WDA_NONE };
+
+		let affinity:u32 = if exclude { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE };
+		if unsafe { SetWindowDisplayAffinity(self.0, affinity) } == 0 {
+			return Err(WindowError::OperationFailed("Could not set window display affinity.".to_string()));
+		}
+		Ok(())
+	}
+	
+	/// Limit the window to a rounded rectangle of the given size, relative to the top-left of the window rect. The window is only drawn and hit-tested inside the region.
+	pub fn set_round_rect_region(&self, width:i32, height:i32, ellipse_width:i32, ellipse_height:i32) -> Result<(), WindowError> {
+		let region:HRGN = unsafe { winapi::um::wingdi::CreateRoundRectRgn(0, 0, width + 1, height + 1, ellipse_width, ellipse_height) };
+		self.apply_region(region)
+	}
+
+	/// Limit the window to the polygon described by the given [x, y] points, relative to the top-left of the window rect. The window is only drawn and hit-tested inside the region.
+	pub fn set_region(&self, points:&[[i32; 2]]) -> Result<(), WindowError> {
+		use winapi::{ shared::windef::POINT, um::wingdi::{ CreatePolygonRgn, WINDING } };
+
+		let points:Vec<POINT> = points.iter().map(|point| POINT { x: point[0], y: point[1] }).collect();
+		let region:HRGN = unsafe { CreatePolygonRgn(points.as_ptr(), points.len() as i32, WINDING) };
+		self.apply_region(region)
+	}
+
+	/// Remove any region from the window, making the entire window visible again.
+	pub fn clear_region(&self) {
+		unsafe { winapi::um::winuser::SetWindowRgn(self.0, std::ptr::null_mut(), 1); }
+	}
+
+	/// Apply a region to the window. After a successful call the system owns the region and deletes it when replaced, so it is only deleted here on failure.
+	fn apply_region(&self, region:HRGN) -> Result<(), WindowError> {
+		if region.is_null() {
+			return Err(WindowError::OperationFailed("Could not create window region.".to_string()));
+		}
+		if unsafe { winapi::um::winuser::SetWindowRgn(self.0, region, 1) } == 0 {
+			unsafe { winapi::um::wingdi::DeleteObject(region as *mut _); }
+			return Err(WindowError::OperationFailed("Could not set window region.".to_string()));
+		}
+		Ok(())
+	}
+
+	/// Fade the opacity of the window from one alpha value to another over the given duration. Blocks until done and leaves the window at the final alpha value.
+	pub fn fade(&self, from:u8, to:u8, duration:Duration) {
+		let (transcolor, _) = self.layered_attributes();
+		let start:Instant = Instant::now();
+		loop {
+			let progress:f64 = if duration.is_zero() { 1.0 } else { (start.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0) };
+			self.set_layered_attributes(transcolor, Some((from as f64 + (to as f64 - from as f64) * progress).round() as u8));
+			if progress >= 1.0 {
+				break;
+			}
+			sleep(FADE_STEP_INTERVAL);
+		}
+	}
+
+	/// Same as `fade`, but runs on a background thread. The returned handle can be joined to wait for the fade to finish.
+	pub fn fade_async(&self, from:u8, to:u8, duration:Duration) -> JoinHandle<()> {
+		let window:WindowController = self.clone();
+		thread::spawn(move || window.fade(from, to, duration))
+	}
+
 	/// Close the window.
 	pub fn close(&self) {
 		unsafe { winapi::um::winuser::PostMessageW(self.0, winapi::um::winuser::WM_CLOSE, 0, 0); }
 	}
 
+	/// Minimize all windows through the taskbar, showing the desktop. Returns false if the taskbar could not be found.
+	pub fn show_desktop() -> bool {
+		WindowController::send_taskbar_command(SHELL_MIN_ALL)
+	}
+
+	/// Undo a previous `show_desktop`, restoring the minimized windows. Returns false if the taskbar could not be found.
+	pub fn undo_show_desktop() -> bool {
+		WindowController::send_taskbar_command(SHELL_MIN_ALL_UNDO)
+	}
+
+	/// Send a WM_COMMAND to the taskbar window.
+	fn send_taskbar_command(command:usize) -> bool {
+		match WindowController::find_by_class("Shell_TrayWnd") {
+			Some(taskbar) => {
+				taskbar.send_message(winapi::um::winuser::WM_COMMAND, command, 0);
+				true
+			},
+			None => false
+		}
+	}
+
+
+
+	/* WAITING METHODS */
+
+	/// Wait until the window exists. Returns whether it exists before the timeout elapsed.
+	pub fn wait_until_exists(&self, timeout:Duration) -> bool {
+		WindowController::poll_until(|| self.exists(), timeout)
+	}
+
+	/// Wait until the window no longer exists. Returns whether it was gone before the timeout elapsed.
+	pub fn wait_for_close(&self, timeout:Duration) -> bool {
+		WindowController::poll_until(|| !self.exists(), timeout)
+	}
+
+	/// Wait until a window with a title containing the given text appears. Returns None if no window was found before the timeout elapsed.
+	pub fn wait_for_title(title:&str, timeout:Duration) -> Option<WindowController> {
+		let title:String = title.to_string();
+		WindowController::wait_for(move |window| window.title().contains(&title), timeout)
+	}
+
+	/// Wait until a window matching the given filter appears. Returns None if no window was found before the timeout elapsed.
+	pub fn wait_for<T:Fn(&WindowController) -> bool + 'static>(filter:T, timeout:Duration) -> Option<WindowController> {
+		let mut found:Option<WindowController> = None;
+		WindowController::poll_until(|| {
+			found = WindowController::find(&filter, 1).into_iter().next();
+			found.is_some()
+		}, timeout);
+		found
+	}
+
+	/// Asynchronously wait until a window with a title containing the given string appears. Polls on a tokio interval instead of blocking a thread. Returns None if no window was found before the timeout elapsed.
+	#[cfg(feature = "tokio")]
+	pub async fn wait_for_title_async(title:&str, timeout:Duration) -> Option<WindowController> {
+		let title:String = title.to_string();
+		WindowController::wait_for_async(move |window| window.title().contains(&title), timeout).await
+	}
+
+	/// Asynchronously wait until a window matching the given filter appears. Polls on a tokio interval instead of blocking a thread. Returns None if no window was found before the timeout elapsed.
+	#[cfg(feature = "tokio")]
+	pub async fn wait_for_async<T:Fn(&WindowController) -> bool + 'static>(filter:T, timeout:Duration) -> Option<WindowController> {
+		let start:Instant = Instant::now();
+		let mut interval:tokio::time::Interval = tokio::time::interval(WAIT_POLL_INTERVAL);
+		loop {
+			interval.tick().await;
+			if let Some(found) = WindowController::find(&filter, 1).into_iter().next() {
+				return Some(found);
+			}
+			if start.elapsed() >= timeout {
+				return None;
+			}
+		}
+	}
+
+	/// Keep checking the condition until it returns true or the timeout elapses. Always checks at least once. Returns whether the condition was met.
+	fn poll_until<T:FnMut() -> bool>(mut condition:T, timeout:Duration) -> bool {
+		let start:Instant = Instant::now();
+		loop {
+			if condition() {
+				return true;
+			}
+			let elapsed:Duration = start.elapsed();
+			if elapsed >= timeout {
+				return false;
+			}
+			sleep(WAIT_POLL_INTERVAL.min(timeout - elapsed));
+		}
+	}
+
 
 
 	/* PROPERTY GETTER METHODS */
@@ -124,6 +553,57 @@ impl WindowController {
 		self.0
 	}
 	
+	/// Get the parent of the window. Returns None for top-level windows.
+	pub fn parent(&self) -> Option<WindowController> {
+		let parent:HWND = unsafe { winapi::um::winuser::GetParent(self.0) };
+		if parent.is_null() {
+			None
+		} else {
+			Some(WindowController(parent))
+		}
+	}
+
+	/// Get the owner of the window. Owned windows stay above their owner and are minimized and destroyed along with it.
+	pub fn owner(&self) -> Option<WindowController> {
+		use winapi::um::winuser::{ GetWindow, GW_OWNER };
+
+		let owner:HWND = unsafe { GetWindow(self.0, GW_OWNER) };
+		if owner.is_null() {
+			None
+		} else {
+			Some(WindowController(owner))
+		}
+	}
+	
+	/// Get all child windows of this window, including nested descendants.
+	pub fn children(&self) -> Vec<WindowController> {
+		use winapi::shared::minwindef::LPARAM;
+
+		let mut children:Vec<WindowController> = Vec::new();
+		unsafe { winapi::um::winuser::EnumChildWindows(self.0, Some(WindowController::externally_get_child_controllers), &mut children as *mut Vec<WindowController> as LPARAM); }
+		children
+	}
+	unsafe extern "system" fn externally_get_child_controllers(hwnd:HWND, control_handle:winapi::shared::minwindef::LPARAM) -> winapi::shared::minwindef::BOOL {
+		unsafe {
+			let children:&mut Vec<WindowController> = &mut *(control_handle as *mut Vec<WindowController>);
+			children.push(WindowController(hwnd));
+			winapi::shared::minwindef::TRUE
+		}
+	}
+	
+	/// Get the z-order index of the window among its siblings. 0 is the topmost window.
+	pub fn z_order(&self) -> usize {
+		use winapi::um::winuser::{ GetWindow, GW_HWNDPREV };
+
+		let mut index:usize = 0;
+		let mut hwnd:HWND = unsafe { GetWindow(self.0, GW_HWNDPREV) };
+		while !hwnd.is_null() {
+			index += 1;
+			hwnd = unsafe { GetWindow(hwnd, GW_HWNDPREV) };
+		}
+		index
+	}
+	
 	/// Check if window is active.
 	pub fn is_active(&self) -> bool {
 		self == &WindowController::active()
@@ -144,6 +624,31 @@ impl WindowController {
 		unsafe { winapi::um::winuser::IsIconic(self.0) != 0 }
 	}
 
+	/// Check if the window is maximized.
+	pub fn is_maximized(&self) -> bool {
+		unsafe { winapi::um::winuser::IsZoomed(self.0) != 0 }
+	}
+
+	/// Check if the window is set to stay on top of all non-topmost windows.
+	pub fn is_topmost(&self) -> bool {
+		use winapi::um::winuser::{ GetWindowLongPtrW, GWL_EXSTYLE, WS_EX_TOPMOST };
+
+		unsafe { GetWindowLongPtrW(self.0, GWL_EXSTYLE) as u32 & WS_EX_TOPMOST != 0 }
+	}
+
+	/// Check if the window processes messages, rather than being hung. Waits up to a second for a response.
+	pub fn is_responding(&self) -> bool {
+		use winapi::um::winuser::{ SendMessageTimeoutW, SMTO_ABORTIFHUNG, WM_NULL };
+
+		let mut result:usize = 0;
+		unsafe { SendMessageTimeoutW(self.0, WM_NULL, 0, 0, SMTO_ABORTIFHUNG, RESPONSE_TIMEOUT_MS, &mut result) != 0 }
+	}
+
+	/// Check if the window accepts mouse and keyboard input.
+	pub fn is_enabled(&self) -> bool {
+		unsafe { winapi::um::winuser::IsWindowEnabled(self.0) != 0 }
+	}
+
 	/// Get the process ID of the window.
 	pub fn pid(&self) -> u32 {
 		let mut pid:winapi::shared::minwindef::DWORD = 0;
@@ -151,6 +656,16 @@ impl WindowController {
 		pid as u32
 	}
 
+	/// Get the ID of the thread that created the window.
+	pub fn thread_id(&self) -> u32 {
+		unsafe { winapi::um::winuser::GetWindowThreadProcessId(self.0, std::ptr::null_mut()) }
+	}
+
+	/// Get the user data stored on the window. Note that the application owning the window might use this value itself.
+	pub fn user_data(&self) -> isize {
+		unsafe { winapi::um::winuser::GetWindowLongPtrW(self.0, winapi::um::winuser::GWLP_USERDATA) }
+	}
+
 	/// Get the ID of the window.
 	pub fn id(&self) -> u32 {
 		unsafe { winapi::um::winuser::GetDlgCtrlID(self.0) as u32 }
@@ -158,61 +673,254 @@ impl WindowController {
 
 	/// Get the title of the window.
 	pub fn title(&self) -> String {
+		use winapi::um::winuser::{ GetWindowTextLengthW, GetWindowTextW };
+
 		unsafe {
-			let mut buffer:[u16; 255] = [0u16; 255];
-			let length:i32 = winapi::um::winuser::GetWindowTextW(self.0, buffer.as_mut_ptr(), buffer.len() as i32);
-			(0..length as usize).map(|index| buffer[index] as u8 as char).collect::<String>()
+			let expected_length:i32 = GetWindowTextLengthW(self.0);
+			if expected_length <= 0 {
+				return String::new();
+			}
+			let mut buffer:Vec<u16> = vec![0u16; expected_length as usize + 1];
+			let length:i32 = GetWindowTextW(self.0, buffer.as_mut_ptr(), buffer.len() as i32);
+			String::from_utf16_lossy(&buffer[..length.max(0) as usize])
 		}
 	}
 
-	/// Get the class name of the window.
+	/// Get the text content of the window through WM_GETTEXT. For controls like edit boxes, this can return text `title` does not.
+	pub fn control_text(&self) -> String {
+		use winapi::um::winuser::{ WM_GETTEXT, WM_GETTEXTLENGTH };
+
+		let expected_length:isize = self.send_message(WM_GETTEXTLENGTH, 0, 0);
+		if expected_length <= 0 {
+			return String::new();
+		}
+		let mut buffer:Vec<u16> = vec![0u16; expected_length as usize + 1];
+		let length:isize = self.send_message(WM_GETTEXT, buffer.len(), buffer.as_mut_ptr() as isize);
+		String::from_utf16_lossy(&buffer[..(length.max(0) as usize).min(buffer.len())])
+	}
+
+	/// Get the class name of the window. Alias of `class_name`.
 	pub fn class(&self) -> String {
-		unsafe {
-			let mut buffer:[u16; 255] = [0u16; 255];
-			let length:i32 = winapi::um::winuser::GetClassNameW(self.0, buffer.as_mut_ptr(), buffer.len() as i32);
-			(0..length as usize).map(|index| buffer[index] as u8 as char).collect::<String>()
+		self.class_name()
+	}
+
+	/// Get the class name of the window.
+	pub fn class_name(&self) -> String {
+		let mut buffer:Vec<u16> = vec![0u16; 256];
+		loop {
+			let length:i32 = unsafe { winapi::um::winuser::GetClassNameW(self.0, buffer.as_mut_ptr(), buffer.len() as i32) };
+			if length <= 0 {
+				return String::new();
+			}
+
+			// When the name fills the buffer entirely, it might have been truncated.
+			if (length as usize) < buffer.len() - 1 {
+				return String::from_utf16_lossy(&buffer[..length as usize]);
+			}
+			buffer.resize(buffer.len() * 2, 0);
+		}
+	}
+
+	/// Check if the window is an application window, as listed in Alt-Tab.
+	fn is_app_window(&self) -> bool {
+		use winapi::um::winuser::{ GetWindowLongPtrW, GWL_EXSTYLE, WS_EX_APPWINDOW, WS_EX_TOOLWINDOW };
+
+		if !self.is_visible() {
+			return false;
 		}
+		let extended_style_flags:u32 = unsafe { GetWindowLongPtrW(self.0, GWL_EXSTYLE) } as u32;
+		if extended_style_flags & WS_EX_TOOLWINDOW != 0 {
+			return false;
+		}
+		if self.owner().is_some() && extended_style_flags & WS_EX_APPWINDOW == 0 {
+			return false;
+		}
+		!self.is_cloaked()
 	}
 
 	/// Get the process name of the window.
-	pub fn process_name(&self) -> Result<String, Box<dyn Error>> {
+	pub fn process_name(&self) -> Result<String, WindowError> {
 		let path:String = self.exe_path()?;
-		if let Some(last_node) = path.replace('\\', "/").split('/').last() {
+		if let Some(last_node) = path.replace('\\', "/").split('/').next_back() {
 			Ok(last_node.to_owned())
 		} else {
-			Err(format!("Could not get last node in path '{path}'.").into())
+			Err(WindowError::ProcessQueryFailed(format!("Could not get last node in path '{path}'.")))
 		}
 	}
 
 	/// Get the path of the executable the window is based on.
-	pub fn exe_path(&self) -> Result<String, Box<dyn Error>> {
-		use winapi::{ ctypes::c_void, um::{ handleapi::CloseHandle, processthreadsapi::OpenProcess, winbase::QueryFullProcessImageNameW, winnt::PROCESS_QUERY_LIMITED_INFORMATION } };
+	pub fn exe_path(&self) -> Result<String, WindowError> {
+		use winapi::{ ctypes::c_void, shared::winerror::ERROR_INSUFFICIENT_BUFFER, um::{ errhandlingapi::GetLastError, handleapi::CloseHandle, winbase::QueryFullProcessImageNameW, winnt::PROCESS_QUERY_LIMITED_INFORMATION } };
 		use std::{ ffi::OsString, os::windows::ffi::OsStringExt };
 
 		unsafe {
 		
 			// Open the process with PROCESS_QUERY_LIMITED_INFORMATION access.
-			let process_handle:*mut c_void = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, self.pid());
-			if process_handle.is_null() {
-				return Err("Could not open process to find exe path.".into());
-			}
+			let process_handle:*mut c_void = self.open_process(PROCESS_QUERY_LIMITED_INFORMATION)?;
 		
-			// Query the full path of the process executable.
-			let mut buffer_size:u32 = 260; // MAX_PATH size
-			let mut buffer:Vec<u16> = vec![0; buffer_size as usize];
-			let success:i32 = QueryFullProcessImageNameW(process_handle, 0, buffer.as_mut_ptr(), &mut buffer_size);
-			CloseHandle(process_handle);
-			if success == 0 {
-				return Err("Could not get full process image.".into());
+			// Query the full path of the process executable. Starts at MAX_PATH and doubles the buffer for long paths.
+			let mut buffer:Vec<u16> = vec![0; 260];
+			let mut buffer_size:u32;
+			loop {
+				buffer_size = buffer.len() as u32;
+				if QueryFullProcessImageNameW(process_handle, 0, buffer.as_mut_ptr(), &mut buffer_size) != 0 {
+					break;
+				}
+				if GetLastError() != ERROR_INSUFFICIENT_BUFFER || buffer.len() >= MAX_EXE_PATH_LENGTH {
+					CloseHandle(process_handle);
+					return Err(WindowError::ProcessQueryFailed("Could not get full process image.".to_string()));
+				}
+				buffer.resize((buffer.len() * 2).min(MAX_EXE_PATH_LENGTH), 0);
 			}
+			CloseHandle(process_handle);
 			
 			// Convert the buffer into a Rust String.
 			Ok(OsString::from_wide(&buffer[..buffer_size as usize]).to_string_lossy().replace('\\', "/"))
 		}
 	}
 
-	/// Get the position of this window.
+	/// Get the command line the process of the window was started with, read from its process environment block. A 32-bit build can not read the command line of 64-bit processes.
+	pub fn command_line(&self) -> Result<String, WindowError> {
+		use winapi::{ ctypes::c_void, shared::ntdef::UNICODE_STRING, um::{ handleapi::CloseHandle, winnt::{ PROCESS_QUERY_INFORMATION, PROCESS_VM_READ } } };
+		use std::mem;
+
+		unsafe {
+
+			// Open the process with rights to read its memory.
+			let process_handle:*mut c_void = self.open_process(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ)?;
+
+			// Find the process environment block.
+			let mut basic_information:ProcessBasicInformation = mem::zeroed();
+			let status:i32 = NtQueryInformationProcess(process_handle, 0, &mut basic_information as *mut ProcessBasicInformation as *mut c_void, mem::size_of::<ProcessBasicInformation>() as u32, std::ptr::null_mut());
+			if status < 0 {
+				CloseHandle(process_handle);
+				return Err(WindowError::ProcessQueryFailed(format!("Could not query process information, status {status:#X}.")));
+			}
+
+			// Follow the process parameters to the command line string.
+			let command_line:Option<Vec<u16>> = WindowController::read_process_memory::<usize>(process_handle, basic_information.peb_base_address + PEB_PROCESS_PARAMETERS_OFFSET)
+				.and_then(|parameters_address| WindowController::read_process_memory::<UNICODE_STRING>(process_handle, parameters_address + PROCESS_PARAMETERS_COMMAND_LINE_OFFSET))
+				.and_then(|command_line| {
+					let mut buffer:Vec<u16> = vec![0u16; command_line.Length as usize / 2];
+					if buffer.is_empty() || WindowController::read_process_memory_into(process_handle, command_line.Buffer as usize, buffer.as_mut_ptr() as *mut c_void, command_line.Length as usize) {
+						Some(buffer)
+					} else {
+						None
+					}
+				});
+			CloseHandle(process_handle);
+			match command_line {
+				Some(buffer) => Ok(String::from_utf16_lossy(&buffer)),
+				None => Err(WindowError::ProcessQueryFailed("Could not read command line from process memory.".to_string()))
+			}
+		}
+	}
+
+	/// Open the process of the window with the given access rights. Distinguishes windows and processes that are gone from processes that deny access.
+	fn open_process(&self, access:u32) -> Result<winapi::um::winnt::HANDLE, WindowError> {
+		use winapi::{ shared::winerror::{ ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER }, um::{ errhandlingapi::GetLastError, processthreadsapi::OpenProcess } };
+
+		let pid:u32 = self.pid();
+		if pid == 0 || !self.exists() {
+			return Err(WindowError::WindowNotFound);
+		}
+		let process_handle:winapi::um::winnt::HANDLE = unsafe { OpenProcess(access, 0, pid) };
+		if process_handle.is_null() {
+			return Err(match unsafe { GetLastError() } {
+				ERROR_ACCESS_DENIED => WindowError::ProcessAccessDenied,
+				ERROR_INVALID_PARAMETER => WindowError::WindowNotFound, // The process exited.
+				code => WindowError::ProcessOpenFailed(code)
+			});
+		}
+		Ok(process_handle)
+	}
+
+	/// Read a value of the given type from the memory of another process.
+	fn read_process_memory<T>(process_handle:winapi::um::winnt::HANDLE, address:usize) -> Option<T> {
+		let mut value:std::mem::MaybeUninit<T> = std::mem::MaybeUninit::uninit();
+		if WindowController::read_process_memory_into(process_handle, address, value.as_mut_ptr() as *mut winapi::ctypes::c_void, std::mem::size_of::<T>()) {
+			Some(unsafe { value.assume_init() })
+		} else {
+			None
+		}
+	}
+
+	/// Read the given amount of bytes from the memory of another process into the target buffer. Returns whether all bytes were read.
+	fn read_process_memory_into(process_handle:winapi::um::winnt::HANDLE, address:usize, target:*mut winapi::ctypes::c_void, size:usize) -> bool {
+		let mut bytes_read:usize = 0;
+		let success:i32 = unsafe { winapi::um::memoryapi::ReadProcessMemory(process_handle, address as *const winapi::ctypes::c_void, target, size, &mut bytes_read) };
+		success != 0 && bytes_read == size
+	}
+
+	/// Check if the process of the window runs elevated. Input and automation from a non-elevated process do not reach elevated windows.
+	pub fn is_elevated(&self) -> Result<bool, WindowError> {
+		use winapi::{ ctypes::c_void, um::{ handleapi::CloseHandle, processthreadsapi::OpenProcessToken, securitybaseapi::GetTokenInformation, winnt::{ TokenElevation, PROCESS_QUERY_LIMITED_INFORMATION, TOKEN_ELEVATION, TOKEN_QUERY } } };
+		use std::{ mem, ptr };
+
+		unsafe {
+
+			// Open the process with PROCESS_QUERY_LIMITED_INFORMATION access.
+			let process_handle:*mut c_void = self.open_process(PROCESS_QUERY_LIMITED_INFORMATION)?;
+
+			// Open the access token of the process.
+			let mut token_handle:*mut c_void = ptr::null_mut();
+			let success:i32 = OpenProcessToken(process_handle, TOKEN_QUERY, &mut token_handle);
+			CloseHandle(process_handle);
+			if success == 0 {
+				return Err(WindowError::ProcessQueryFailed("Could not open process token.".to_string()));
+			}
+
+			// Read the elevation from the token.
+			let mut elevation:TOKEN_ELEVATION = TOKEN_ELEVATION { TokenIsElevated: 0 };
+			let mut returned_size:u32 = 0;
+			let success:i32 = GetTokenInformation(token_handle, TokenElevation, &mut elevation as *mut TOKEN_ELEVATION as *mut c_void, mem::size_of::<TOKEN_ELEVATION>() as u32, &mut returned_size);
+			CloseHandle(token_handle);
+			if success == 0 {
+				return Err(WindowError::ProcessQueryFailed("Could not get token elevation.".to_string()));
+			}
+			Ok(elevation.TokenIsElevated != 0)
+		}
+	}
+
+	/// Get the [x, y, w, h] position of the client area of this window in screen coordinates. Same as `client_rect`.
 	pub fn position(&self) -> [i32; 4] {
+		self.client_rect()
+	}
+
+	/// Get the [x, y, w, h] position of the outer frame of this window in screen coordinates. Matches the coordinates used by `set_pos`.
+	pub fn window_rect(&self) -> [i32; 4] {
+		use winapi::{ shared::windef::RECT, um::winuser::GetWindowRect };
+
+		let mut window_rect:RECT = RECT { left: 0, top: 0, right: 0, bottom: 0 };
+		unsafe { GetWindowRect(self.hwnd(), &mut window_rect); }
+		[window_rect.left, window_rect.top, window_rect.right - window_rect.left, window_rect.bottom - window_rect.top]
+	}
+
+	/// Get the [x, y, w, h] position of the visible frame of this window in screen coordinates. Unlike `window_rect`, excludes the invisible resize borders.
+	pub fn dwm_frame_rect(&self) -> Result<[i32; 4], WindowError> {
+		use winapi::{ shared::{ minwindef::DWORD, windef::RECT }, um::dwmapi::{ DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS } };
+		use std::mem;
+
+		let mut frame_rect:RECT = RECT { left: 0, top: 0, right: 0, bottom: 0 };
+		let result:i32 = unsafe { DwmGetWindowAttribute(self.hwnd(), DWMWA_EXTENDED_FRAME_BOUNDS, &mut frame_rect as *mut RECT as *mut _, mem::size_of::<RECT>() as DWORD) };
+		if result != 0 {
+			return Err(if self.exists() { WindowError::OperationFailed(format!("Could not get DWM frame bounds, error code 0x{:08X}.", result)) } else { WindowError::WindowNotFound });
+		}
+		Ok([frame_rect.left, frame_rect.top, frame_rect.right - frame_rect.left, frame_rect.bottom - frame_rect.top])
+	}
+
+	/// Get the DPI of the window, 96 being 100% scaling. Only reflects the monitor the window is on when this process is per-monitor DPI aware. Returns 0 for invalid windows.
+	pub fn dpi(&self) -> u32 {
+		unsafe { winapi::um::winuser::GetDpiForWindow(self.0) }
+	}
+
+	/// Get the scale factor of the window, 1.0 being 100% scaling. Multiply logical coordinates by this factor to get physical coordinates. Like `dpi`, requires this process to be per-monitor DPI aware.
+	pub fn scale_factor(&self) -> f64 {
+		self.dpi() as f64 / 96.0
+	}
+
+	/// Get the [x, y, w, h] position of the client area of this window in screen coordinates. Excludes the frame and caption.
+	pub fn client_rect(&self) -> [i32; 4] {
 		use winapi::{ shared::windef::{ RECT, POINT }, um::winuser::{ GetClientRect, ClientToScreen } };
 
 		let mut client_rect:RECT = RECT { left: 0, top: 0, right: 0, bottom: 0 };
@@ -237,4 +945,117 @@ impl WindowController {
 	pub fn style(&self) -> WindowStyle {
 		WindowStyle::new(self.clone())
 	}
-}
\ No newline at end of file
+}
+impl std::fmt::Debug for WindowController {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.exists() {
+			write!(f, "WindowController {{ hwnd: 0x{:08X}, title: {:?}, pid: {} }}", self.0 as usize, self.title(), self.pid())
+		} else {
+			write!(f, "WindowController {{ hwnd: 0x{:08X}, title: <destroyed>, pid: <destroyed> }}", self.0 as usize)
+		}
+	}
+}
+impl From<HWND> for WindowController {
+	fn from(hwnd:HWND) -> WindowController {
+		WindowController(hwnd)
+	}
+}
+impl From<WindowController> for HWND {
+	fn from(window:WindowController) -> HWND {
+		window.0
+	}
+}
+impl PartialEq<HWND> for WindowController {
+	fn eq(&self, other:&HWND) -> bool {
+		self.0 == *other
+	}
+}
+#[cfg(feature = "raw-window-handle")]
+impl raw_window_handle::HasWindowHandle for WindowController {
+	fn window_handle(&self) -> Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+		use raw_window_handle::{ HandleError, RawWindowHandle, Win32WindowHandle, WindowHandle };
+		use std::num::NonZeroIsize;
+
+		let hwnd:NonZeroIsize = NonZeroIsize::new(self.0 as isize).ok_or(HandleError::Unavailable)?;
+		Ok(unsafe { WindowHandle::borrow_raw(RawWindowHandle::Win32(Win32WindowHandle::new(hwnd))) })
+	}
+}
+#[cfg(feature = "raw-window-handle")]
+impl raw_window_handle::HasDisplayHandle for WindowController {
+	fn display_handle(&self) -> Result<raw_window_handle::DisplayHandle<'_>, raw_window_handle::HandleError> {
+		Ok(raw_window_handle::DisplayHandle::windows())
+	}
+}
+
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_send_sync<T:Send + Sync>() {}
+
+	/// Create a visible top-level test window at the given [x, y, w, h] position.
+	fn create_test_window(position:[i32; 4]) -> WindowController {
+		use winapi::um::winuser::{ CreateWindowExW, WS_OVERLAPPEDWINDOW, WS_VISIBLE };
+		use std::ptr;
+
+		let class_name:Vec<u16> = "STATIC".encode_utf16().chain(Some(0)).collect();
+		let title:Vec<u16> = "window_controller test".encode_utf16().chain(Some(0)).collect();
+		let hwnd:HWND = unsafe { CreateWindowExW(0, class_name.as_ptr(), title.as_ptr(), WS_OVERLAPPEDWINDOW | WS_VISIBLE, position[0], position[1], position[2], position[3], ptr::null_mut(), ptr::null_mut(), ptr::null_mut(), ptr::null_mut()) };
+		assert!(!hwnd.is_null(), "Could not create test window.");
+		WindowController::from_hwnd(hwnd)
+	}
+
+	#[test]
+	fn controller_is_send_and_sync() {
+		assert_send_sync::<WindowController>();
+	}
+
+	#[test]
+	fn controller_can_be_used_from_another_thread() {
+		use winapi::um::winuser::{ DestroyWindow, DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE };
+		use std::{ mem, ptr };
+
+		let window:WindowController = create_test_window([100, 100, 400, 300]);
+		let hwnd:HWND = window.hwnd();
+		let title_thread:JoinHandle<String> = thread::spawn(move || window.title());
+
+		// Getting the title from another thread sends a message to the window, which this thread has to handle.
+		while !title_thread.is_finished() {
+			unsafe {
+				let mut message:MSG = mem::zeroed();
+				while PeekMessageW(&mut message, ptr::null_mut(), 0, 0, PM_REMOVE) != 0 {
+					TranslateMessage(&message);
+					DispatchMessageW(&message);
+				}
+			}
+			sleep(Duration::from_millis(1));
+		}
+		let title:String = title_thread.join().unwrap();
+		unsafe { DestroyWindow(hwnd); }
+		assert_eq!(title, "window_controller test");
+	}
+
+	#[test]
+	#[ignore = "requires an interactive desktop"]
+	fn activate_keeps_window_rect() {
+		let window:WindowController = create_test_window([100, 100, 800, 600]);
+		let rect_before:[i32; 4] = window.window_rect();
+		window.activate();
+		let rect_after:[i32; 4] = window.window_rect();
+		unsafe { winapi::um::winuser::DestroyWindow(window.hwnd()); }
+		assert_eq!(rect_before, [100, 100, 800, 600]);
+		assert_eq!(rect_after, rect_before);
+	}
+
+	#[test]
+	fn find_all_drops_filter() {
+		use std::sync::Arc;
+
+		let captured:Arc<()> = Arc::new(());
+		let filter_captured:Arc<()> = captured.clone();
+		WindowController::find_all(move |_| { let _ = &filter_captured; false });
+		assert_eq!(Arc::strong_count(&captured), 1);
+	}
+}
@@ -0,0 +1,85 @@
+use winapi::{ shared::minwindef::{ BOOL, DWORD }, um::dwmapi::{ DwmGetWindowAttribute, DwmSetWindowAttribute } };
+use std::mem;
+use crate::{ WindowController, WindowError };
+
+
+
+const DWMWA_USE_IMMERSIVE_DARK_MODE:DWORD = 20;
+const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1:DWORD = 19;
+const DWMWA_WINDOW_CORNER_PREFERENCE:DWORD = 33;
+const DWMWA_SYSTEMBACKDROP_TYPE:DWORD = 38;
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CornerStyle {
+	Default = 0,
+	DoNotRound = 1,
+	Round = 2,
+	RoundSmall = 3
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BackdropKind {
+	Auto = 0,
+	None = 1,
+	Mica = 2, // DWMSBT_MAINWINDOW
+	Acrylic = 3, // DWMSBT_TRANSIENTWINDOW
+	Tabbed = 4 // DWMSBT_TABBEDWINDOW
+}
+
+
+
+impl WindowController {
+
+	/* DWM METHODS */
+
+	/// Toggle the dark-mode title bar of the window. Fails on Windows versions that do not support dark title bars.
+	pub fn set_dark_titlebar(&self, dark:bool) -> Result<(), WindowError> {
+		let value:BOOL = dark as BOOL;
+		self.set_dwm_attribute(DWMWA_USE_IMMERSIVE_DARK_MODE, &value)
+			.or_else(|_| self.set_dwm_attribute(DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1, &value))
+	}
+
+	/// Set the corner rounding preference of the window. Ignored on Windows versions before Windows 11.
+	pub fn set_corner_style(&self, preference:CornerStyle) {
+		let value:DWORD = preference as DWORD;
+		let _ = self.set_dwm_attribute(DWMWA_WINDOW_CORNER_PREFERENCE, &value);
+	}
+
+	/// Set the system-drawn backdrop material behind the window. Fails on Windows versions before Windows 11 22H2. The backdrop only shows through parts of the window that are not painted over.
+	pub fn set_backdrop(&self, kind:BackdropKind) -> Result<(), WindowError> {
+		let value:DWORD = kind as DWORD;
+		self.set_dwm_attribute(DWMWA_SYSTEMBACKDROP_TYPE, &value)
+	}
+
+	/// Check if the window is cloaked by DWM, meaning it is not drawn even when visible. Windows on other virtual desktops and suspended UWP apps are cloaked.
+	pub fn is_cloaked(&self) -> bool {
+		use winapi::um::dwmapi::DWMWA_CLOAKED;
+
+		self.get_dwm_attribute::<DWORD>(DWMWA_CLOAKED).map(|cloaked| cloaked != 0).unwrap_or(false)
+	}
+
+
+
+	/* HELPER METHODS */
+
+	/// Set a DWM window attribute.
+	fn set_dwm_attribute<T>(&self, attribute:DWORD, value:&T) -> Result<(), WindowError> {
+		let result:i32 = unsafe { DwmSetWindowAttribute(self.hwnd(), attribute, value as *const T as *const _, mem::size_of::<T>() as DWORD) };
+		if result != 0 {
+			return Err(if self.exists() { WindowError::DwmUnsupported(result) } else { WindowError::WindowNotFound });
+		}
+		Ok(())
+	}
+
+	/// Get a DWM window attribute.
+	fn get_dwm_attribute<T:Default>(&self, attribute:DWORD) -> Result<T, WindowError> {
+		let mut value:T = T::default();
+		let result:i32 = unsafe { DwmGetWindowAttribute(self.hwnd(), attribute, &mut value as *mut T as *mut _, mem::size_of::<T>() as DWORD) };
+		if result != 0 {
+			return Err(if self.exists() { WindowError::DwmUnsupported(result) } else { WindowError::WindowNotFound });
+		}
+		Ok(value)
+	}
+}
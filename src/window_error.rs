@@ -0,0 +1,48 @@
+use std::{ error::Error, fmt, io };
+
+
+
+#[derive(Debug)]
+pub enum WindowError {
+	WindowNotFound, // The window or its process no longer exists.
+	ProcessAccessDenied,
+	ProcessOpenFailed(u32), // Win32 error code returned by GetLastError.
+	ProcessQueryFailed(String),
+	ImageCaptureFailed(String),
+	InvalidBounds(String),
+	DwmUnsupported(i32), // HRESULT returned by DWM.
+	ClipboardFailed(String),
+	ImageEncodingFailed(String),
+	Io(io::Error),
+	OperationFailed(String)
+}
+impl fmt::Display for WindowError {
+	fn fmt(&self, f:&mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			WindowError::WindowNotFound => write!(f, "The window or its process no longer exists."),
+			WindowError::ProcessAccessDenied => write!(f, "Access to the process of the window was denied."),
+			WindowError::ProcessOpenFailed(code) => write!(f, "Could not open the process of the window, error code {code}."),
+			WindowError::ProcessQueryFailed(reason) => write!(f, "Could not query the process of the window: {reason}"),
+			WindowError::ImageCaptureFailed(reason) => write!(f, "Could not capture window image: {reason}"),
+			WindowError::InvalidBounds(reason) => write!(f, "Invalid bounds: {reason}"),
+			WindowError::DwmUnsupported(code) => write!(f, "DWM call failed with error code 0x{:08X}, the requested attribute might not be supported by this version of Windows.", code),
+			WindowError::ClipboardFailed(reason) => write!(f, "Could not copy to clipboard: {reason}"),
+			WindowError::ImageEncodingFailed(reason) => write!(f, "Could not encode image: {reason}"),
+			WindowError::Io(error) => write!(f, "I/O error: {error}"),
+			WindowError::OperationFailed(reason) => write!(f, "Window operation failed: {reason}")
+		}
+	}
+}
+impl Error for WindowError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			WindowError::Io(error) => Some(error),
+			_ => None
+		}
+	}
+}
+impl From<io::Error> for WindowError {
+	fn from(error:io::Error) -> WindowError {
+		WindowError::Io(error)
+	}
+}
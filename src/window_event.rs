@@ -0,0 +1,149 @@
+use winapi::{ shared::{ minwindef::DWORD, ntdef::LONG, windef::{ HWINEVENTHOOK, HWINEVENTHOOK__, HWND } }, um::{ processthreadsapi::GetCurrentThreadId, winuser::{ DispatchMessageW, GetMessageW, PeekMessageW, PostQuitMessage, PostThreadMessageW, SetWinEventHook, TranslateMessage, UnhookWinEvent, EVENT_OBJECT_CREATE, EVENT_OBJECT_DESTROY, EVENT_OBJECT_LOCATIONCHANGE, EVENT_SYSTEM_FOREGROUND, GA_PARENT, GetAncestor, GetDesktopWindow, MSG, CHILDID_SELF, OBJID_WINDOW, PM_NOREMOVE, WINEVENT_OUTOFCONTEXT, WM_QUIT, WM_USER } } };
+use std::{ cell::RefCell, collections::{ HashSet, VecDeque }, mem, panic::{ self, AssertUnwindSafe }, ptr, sync::mpsc, thread::{ self, JoinHandle } };
+use crate::{ WindowController, WindowError };
+
+
+
+pub enum WindowLifecycleEvent {
+	Created(WindowController),
+	Destroyed(WindowController)
+}
+
+
+
+type EventHandler = Box<dyn FnMut(DWORD, HWND, LONG, LONG) + Send>;
+
+thread_local! {
+	static EVENT_HANDLER:RefCell<Option<EventHandler>> = RefCell::new(None);
+	static PENDING_EVENTS:RefCell<VecDeque<(DWORD, HWND, LONG, LONG)>> = const { RefCell::new(VecDeque::new()) };
+}
+
+
+
+pub struct EventSubscription {
+	thread_id:DWORD,
+	thread:Option<JoinHandle<()>>
+}
+impl EventSubscription {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Install a WinEvent hook for the given event range on a dedicated thread. Process and thread ID 0 listen to all processes and threads. The handler is created on the hook thread once the hook is installed, and receives the event, window, object ID and child ID.
+	fn new<T:FnOnce() -> EventHandler + Send + 'static>(event_min:DWORD, event_max:DWORD, process_id:DWORD, thread_id:DWORD, create_handler:T) -> Result<EventSubscription, WindowError> {
+		let (thread_id_sender, thread_id_receiver) = mpsc::channel::<Option<DWORD>>();
+		let thread:JoinHandle<()> = thread::spawn(move || {
+			unsafe {
+
+				// Make sure the thread has a message queue before handing out its ID, so the quit message can always be posted.
+				let mut message:MSG = mem::zeroed();
+				PeekMessageW(&mut message, ptr::null_mut(), WM_USER, WM_USER, PM_NOREMOVE);
+
+				// Install the hook. Out-of-context callbacks are called on this thread while it pumps messages. Only hands out the thread ID once the hook is installed.
+				let hook:*mut HWINEVENTHOOK__ = SetWinEventHook(event_min, event_max, ptr::null_mut(), Some(EventSubscription::externally_handle_event), process_id, thread_id, WINEVENT_OUTOFCONTEXT);
+				if hook.is_null() {
+					let _ = thread_id_sender.send(None);
+					return;
+				}
+				EVENT_HANDLER.with(|event_handler| *event_handler.borrow_mut() = Some(create_handler()));
+				let _ = thread_id_sender.send(Some(GetCurrentThreadId()));
+
+				// Pump messages until the subscription is dropped.
+				while GetMessageW(&mut message, ptr::null_mut(), 0, 0) > 0 {
+					TranslateMessage(&message);
+					DispatchMessageW(&message);
+				}
+				UnhookWinEvent(hook);
+			}
+		});
+		match thread_id_receiver.recv() {
+			Ok(Some(thread_id)) => Ok(EventSubscription {
+				thread_id,
+				thread: Some(thread)
+			}),
+			_ => {
+				let _ = thread.join();
+				Err(WindowError::OperationFailed("Could not install WinEvent hook.".to_string()))
+			}
+		}
+	}
+	unsafe extern "system" fn externally_handle_event(_hook:HWINEVENTHOOK, event:DWORD, hwnd:HWND, object_id:LONG, child_id:LONG, _event_thread:DWORD, _event_time:DWORD) {
+
+		// Queue the event. Calls that wait on messages, like sending WM_GETTEXT, can re-enter this callback while the handler runs. The outer call handles the queued events once the handler returns.
+		PENDING_EVENTS.with(|pending_events| pending_events.borrow_mut().push_back((event, hwnd, object_id, child_id)));
+		let Some(mut handler) = EVENT_HANDLER.with(|event_handler| event_handler.borrow_mut().take()) else {
+			return;
+		};
+		while let Some((event, hwnd, object_id, child_id)) = PENDING_EVENTS.with(|pending_events| pending_events.borrow_mut().pop_front()) {
+
+			// Panics can not unwind out of the callback. A handler that panicked ends the subscription by stopping the message pump.
+			if panic::catch_unwind(AssertUnwindSafe(|| handler(event, hwnd, object_id, child_id))).is_err() {
+				PENDING_EVENTS.with(|pending_events| pending_events.borrow_mut().clear());
+				unsafe { PostQuitMessage(0); }
+				return;
+			}
+		}
+		EVENT_HANDLER.with(|event_handler| *event_handler.borrow_mut() = Some(handler));
+	}
+
+
+
+	/* PROPERTY GETTER METHODS */
+
+	/// Check if the subscription is still listening. Stops being active when the event handler panics.
+	pub fn is_active(&self) -> bool {
+		self.thread.as_ref().is_some_and(|thread| !thread.is_finished())
+	}
+}
+impl Drop for EventSubscription {
+	fn drop(&mut self) {
+		unsafe { PostThreadMessageW(self.thread_id, WM_QUIT, 0, 0); }
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+
+
+impl WindowController {
+
+	/// Call the given callback whenever another window comes to the foreground. Keeps listening until the returned subscription is dropped, or until the callback panics.
+	pub fn on_foreground_change<F:FnMut(WindowController) + Send + 'static>(mut callback:F) -> Result<EventSubscription, WindowError> {
+		EventSubscription::new(EVENT_SYSTEM_FOREGROUND, EVENT_SYSTEM_FOREGROUND, 0, 0, move || Box::new(move |_event, hwnd, _object_id, _child_id| callback(WindowController::from_hwnd(hwnd))) as EventHandler)
+	}
+
+	/// Call the given callback whenever a top-level window is created or destroyed. Keeps listening until the returned subscription is dropped, or until the callback panics.
+	pub fn on_create_destroy<F:FnMut(WindowLifecycleEvent) + Send + 'static>(mut callback:F) -> Result<EventSubscription, WindowError> {
+		EventSubscription::new(EVENT_OBJECT_CREATE, EVENT_OBJECT_DESTROY, 0, 0, move || {
+
+			// Destroyed windows can no longer be checked for being top-level, so keep track of the top-level windows seen so far. The snapshot is taken once the hook is installed, so no window is missed.
+			let mut top_level_windows:HashSet<usize> = WindowController::iter().map(|window| window.hwnd() as usize).collect();
+			Box::new(move |event:DWORD, hwnd:HWND, object_id:LONG, child_id:LONG| {
+
+				// Only handle events for windows themselves, not for their contents.
+				if hwnd.is_null() || object_id != OBJID_WINDOW || child_id != CHILDID_SELF {
+					return;
+				}
+
+				let window:WindowController = WindowController::from_hwnd(hwnd);
+				if event == EVENT_OBJECT_CREATE {
+					if window.exists() && unsafe { GetAncestor(hwnd, GA_PARENT) == GetDesktopWindow() } && top_level_windows.insert(hwnd as usize) {
+						callback(WindowLifecycleEvent::Created(window));
+					}
+				} else if top_level_windows.remove(&(hwnd as usize)) {
+					callback(WindowLifecycleEvent::Destroyed(window));
+				}
+			}) as EventHandler
+		})
+	}
+
+	/// Call the given callback with the new [x, y, w, h] window rect whenever this window moves or resizes. Keeps listening until the returned subscription is dropped, or until the callback panics.
+	pub fn on_move_resize<F:FnMut([i32; 4]) + Send + 'static>(&self, mut callback:F) -> Result<EventSubscription, WindowError> {
+		let window:WindowController = self.clone();
+		EventSubscription::new(EVENT_OBJECT_LOCATIONCHANGE, EVENT_OBJECT_LOCATIONCHANGE, self.pid(), 0, move || Box::new(move |_event, hwnd, object_id, child_id| {
+			if hwnd == window.hwnd() && object_id == OBJID_WINDOW && child_id == CHILDID_SELF {
+				callback(window.window_rect());
+			}
+		}) as EventHandler)
+	}
+}
@@ -0,0 +1,177 @@
+use winapi::{ ctypes::c_void, shared::windef::{ HBITMAP, HICON, HWND } };
+use std::{ collections::HashMap, mem, ptr, sync::{ LazyLock, Mutex, MutexGuard } };
+use crate::{ unpremultiply, WindowController, WindowError, WindowImage, DI_NORMAL };
+
+
+
+const DI_MASK:u32 = 0x0001; // Not defined by winapi.
+const ICON_QUERY_TIMEOUT_MS:u32 = 1000;
+static CREATED_ICONS:LazyLock<Mutex<HashMap<usize, usize>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+
+
+impl WindowController {
+
+	/* ICON METHODS */
+
+	/// Set the icon of the window, shown in the title bar and taskbar. The icon previously set through this method is destroyed, icons set by the application itself are left alone.
+	pub fn set_icon(&self, icon:&WindowImage) -> Result<(), WindowError> {
+		use winapi::um::{ wingdi::{ CreateBitmap, DeleteObject }, winuser::{ CreateIconIndirect, DestroyIcon, ICONINFO, ICON_BIG, ICON_SMALL, WM_SETICON } };
+
+		if icon.width == 0 || icon.height == 0 || icon.data.len() != icon.width * icon.height {
+			return Err(WindowError::InvalidBounds(format!("Icon image of {}x{} does not hold valid data.", icon.width, icon.height)));
+		}
+		unsafe {
+
+			// Create the color bitmap from the image and an empty mask, the alpha channel of the color bitmap defines the transparency.
+			let (color_bitmap, color_bits) = create_dib_section(icon.width as i32, icon.height as i32)?;
+			ptr::copy_nonoverlapping(icon.data.as_ptr(), color_bits, icon.data.len());
+			let mask_bits:Vec<u8> = vec![0; icon.width.div_ceil(16) * 2 * icon.height]; // Monochrome rows are aligned to 16 bits.
+			let mask_bitmap:HBITMAP = CreateBitmap(icon.width as i32, icon.height as i32, 1, 1, mask_bits.as_ptr() as *const c_void);
+
+			// Create the icon. The icon keeps its own copy of the bitmaps.
+			let mut icon_info:ICONINFO = ICONINFO { fIcon: 1, xHotspot: 0, yHotspot: 0, hbmMask: mask_bitmap, hbmColor: color_bitmap };
+			let icon_handle:HICON = CreateIconIndirect(&mut icon_info);
+			DeleteObject(color_bitmap as *mut c_void);
+			DeleteObject(mask_bitmap as *mut c_void);
+			if icon_handle.is_null() {
+				return Err(WindowError::OperationFailed("Could not create icon.".to_string()));
+			}
+
+			// Set the icon and destroy the icon previously created for this window. Icons created for windows that no longer exist are destroyed as well.
+			self.send_message(WM_SETICON, ICON_SMALL as usize, icon_handle as isize);
+			self.send_message(WM_SETICON, ICON_BIG as usize, icon_handle as isize);
+			let mut created_icons:MutexGuard<'_, HashMap<usize, usize>> = CREATED_ICONS.lock().unwrap();
+			created_icons.retain(|hwnd, created_icon| {
+				let exists:bool = WindowController::from_hwnd(*hwnd as HWND).exists();
+				if !exists {
+					DestroyIcon(*created_icon as HICON);
+				}
+				exists
+			});
+			if let Some(previous_icon) = created_icons.insert(self.hwnd() as usize, icon_handle as usize) {
+				DestroyIcon(previous_icon as HICON);
+			}
+		}
+		Ok(())
+	}
+
+	/// Get the icon of the window as an image. Uses the icon set on the window, falling back to the icon of the window class. Keeps the alpha channel of the icon.
+	pub fn icon(&self) -> Result<WindowImage, WindowError> {
+		use winapi::um::{ wingdi::{ DeleteObject, GetObjectW, BITMAP }, winuser::{ GetIconInfo, ICONINFO } };
+
+		let icon_handle:HICON = self.icon_handle().ok_or(WindowError::OperationFailed("Window has no icon.".to_string()))?;
+		unsafe {
+
+			// Find the size of the icon through its bitmaps. Icons without a color bitmap store the mask and image stacked in the mask.
+			let mut icon_info:ICONINFO = mem::zeroed();
+			if GetIconInfo(icon_handle, &mut icon_info) == 0 {
+				return Err(WindowError::OperationFailed("Could not get icon info.".to_string()));
+			}
+			let mut bitmap:BITMAP = mem::zeroed();
+			let size_bitmap:HBITMAP = if icon_info.hbmColor.is_null() { icon_info.hbmMask } else { icon_info.hbmColor };
+			let got_size:i32 = GetObjectW(size_bitmap as *mut c_void, mem::size_of::<BITMAP>() as i32, &mut bitmap as *mut BITMAP as *mut c_void);
+			let width:i32 = bitmap.bmWidth;
+			let height:i32 = if icon_info.hbmColor.is_null() { bitmap.bmHeight / 2 } else { bitmap.bmHeight };
+			if !icon_info.hbmColor.is_null() {
+				DeleteObject(icon_info.hbmColor as *mut c_void);
+			}
+			if !icon_info.hbmMask.is_null() {
+				DeleteObject(icon_info.hbmMask as *mut c_void);
+			}
+			if got_size == 0 || width <= 0 || height <= 0 {
+				return Err(WindowError::OperationFailed("Could not get icon size.".to_string()));
+			}
+
+			// Draw the icon. Icons without alpha channel get their transparency from the mask instead.
+			let mut pixels:Vec<u32> = draw_icon(icon_handle, width, height, DI_NORMAL)?;
+			if pixels.iter().all(|pixel| pixel >> 24 == 0) {
+				let mask:Vec<u32> = draw_icon(icon_handle, width, height, DI_MASK)?;
+				for (pixel, mask_pixel) in pixels.iter_mut().zip(mask) {
+					*pixel = if mask_pixel & 0x00FFFFFF == 0 { *pixel | 0xFF000000 } else { 0 };
+				}
+			} else {
+				pixels.iter_mut().for_each(|pixel| *pixel = unpremultiply(*pixel));
+			}
+
+			Ok(WindowImage {
+				data: pixels,
+				width: width as usize,
+				height: height as usize
+			})
+		}
+	}
+
+	/// Get the handle of the icon of the window. Prefers the large icon set on the window, then the small ones, then the icons of the window class.
+	fn icon_handle(&self) -> Option<HICON> {
+		use winapi::um::winuser::{ GetClassLongPtrW, SendMessageTimeoutW, GCLP_HICON, GCLP_HICONSM, ICON_BIG, ICON_SMALL, ICON_SMALL2, SMTO_ABORTIFHUNG, WM_GETICON };
+
+		for icon_type in [ICON_BIG, ICON_SMALL2, ICON_SMALL] {
+			let mut icon_handle:usize = 0;
+			let success:isize = unsafe { SendMessageTimeoutW(self.hwnd(), WM_GETICON, icon_type as usize, 0, SMTO_ABORTIFHUNG, ICON_QUERY_TIMEOUT_MS, &mut icon_handle) };
+			if success != 0 && icon_handle != 0 {
+				return Some(icon_handle as HICON);
+			}
+		}
+		for class_index in [GCLP_HICON, GCLP_HICONSM] {
+			let icon_handle:usize = unsafe { GetClassLongPtrW(self.hwnd(), class_index) };
+			if icon_handle != 0 {
+				return Some(icon_handle as HICON);
+			}
+		}
+		None
+	}
+}
+
+
+
+/// Draw an icon onto a transparent bitmap of the given size with the given DrawIconEx flags. Returns the 0xAARRGGBB pixels, with premultiplied alpha.
+fn draw_icon(icon_handle:HICON, width:i32, height:i32, flags:u32) -> Result<Vec<u32>, WindowError> {
+	use winapi::{ shared::windef::HDC, um::{ wingdi::{ CreateCompatibleDC, DeleteDC, DeleteObject, SelectObject }, winuser::DrawIconEx } };
+
+	unsafe {
+		let dc:HDC = CreateCompatibleDC(ptr::null_mut());
+		if dc.is_null() {
+			return Err(WindowError::OperationFailed("Could not create device context.".to_string()));
+		}
+		let (bitmap, bits) = match create_dib_section(width, height) {
+			Ok(section) => section,
+			Err(error) => {
+				DeleteDC(dc);
+				return Err(error);
+			}
+		};
+		let previous_object:*mut c_void = SelectObject(dc, bitmap as *mut c_void);
+		let success:i32 = DrawIconEx(dc, 0, 0, icon_handle, width, height, 0, ptr::null_mut(), flags);
+		let pixels:Vec<u32> = std::slice::from_raw_parts(bits, (width * height) as usize).to_vec();
+		SelectObject(dc, previous_object);
+		DeleteObject(bitmap as *mut c_void);
+		DeleteDC(dc);
+		if success == 0 {
+			return Err(WindowError::OperationFailed("Could not draw icon.".to_string()));
+		}
+		Ok(pixels)
+	}
+}
+
+/// Create a top-down 32-bit DIB section of the given size. Returns the bitmap and a pointer to its pixels.
+fn create_dib_section(width:i32, height:i32) -> Result<(HBITMAP, *mut u32), WindowError> {
+	use winapi::um::wingdi::{ CreateDIBSection, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS };
+
+	unsafe {
+		let mut bitmap_info:BITMAPINFO = mem::zeroed();
+		bitmap_info.bmiHeader.biSize = mem::size_of::<BITMAPINFOHEADER>() as u32;
+		bitmap_info.bmiHeader.biWidth = width;
+		bitmap_info.bmiHeader.biHeight = -height; // Negative to get data top-down.
+		bitmap_info.bmiHeader.biPlanes = 1;
+		bitmap_info.bmiHeader.biBitCount = 32;
+		bitmap_info.bmiHeader.biCompression = BI_RGB;
+
+		let mut bits:*mut c_void = ptr::null_mut();
+		let bitmap:HBITMAP = CreateDIBSection(ptr::null_mut(), &bitmap_info, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+		if bitmap.is_null() || bits.is_null() {
+			return Err(WindowError::OperationFailed("Could not create DIB section.".to_string()));
+		}
+		Ok((bitmap, bits as *mut u32))
+	}
+}
@@ -1,6 +1,10 @@
-use winapi::{ ctypes::c_void, shared::{ minwindef::DWORD, windef::{ HBITMAP__, HDC__, POINT, RECT } }, um::{ wingdi::{ BI_RGB, BITMAPINFO, BITMAPINFOHEADER, CreateCompatibleBitmap, CreateCompatibleDC, DIB_RGB_COLORS, DeleteDC, DeleteObject, GetDIBits, SelectObject }, winuser::{ ClientToScreen, GetClientRect, GetDC, GetWindowRect, PW_RENDERFULLCONTENT, PrintWindow, ReleaseDC } } };
-use std::{ error::Error, mem };
-use crate::WindowController;
+use std::path::Path;
+use crate::{ CaptureSession, Monitor, Rect, WindowController, WindowError };
+
+
+
+const BLACK_FRAME_THRESHOLD:f64 = 0.95;
+const DIB_HEADER_SIZE:u32 = 40;
 
 
 
@@ -11,136 +15,359 @@ pub struct WindowImage {
 }
 impl WindowImage {
 
+	/// Tries to create an image of the full desktop, spanning all monitors. Includes all visible windows.
+	pub fn capture_desktop() -> Result<WindowImage, WindowError> {
+		use winapi::um::winuser::{ GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN };
+
+		let virtual_screen:[i32; 4] = unsafe { [GetSystemMetrics(SM_XVIRTUALSCREEN), GetSystemMetrics(SM_YVIRTUALSCREEN), GetSystemMetrics(SM_CXVIRTUALSCREEN), GetSystemMetrics(SM_CYVIRTUALSCREEN)] };
+		WindowImage::capture_screen_area(virtual_screen)
+	}
+
+	/// Tries to create an image of a single monitor. Includes all visible windows.
+	pub fn capture_monitor(monitor:&Monitor) -> Result<WindowImage, WindowError> {
+		WindowImage::capture_screen_area(monitor.bounds)
+	}
+
+	/// Tries to create an image of the given [x, y, w, h] area of the screen. The client area of the desktop window starts at the screen origin, so its screen capture covers any screen area.
+	fn capture_screen_area(area:[i32; 4]) -> Result<WindowImage, WindowError> {
+		WindowController::desktop().capture_session()?.capture_screen_with_bounds(area)
+	}
+
 	/// Return the data of the image in a list of rows.
 	pub fn data_2d(&self) -> Vec<&[u32]> {
 		self.data.chunks(self.width).collect()
 	}
-}
 
+	/// Get the 0xAARRGGBB value of the pixel at the given position. Returns None if the position is out of bounds.
+	pub fn get_pixel(&self, x:usize, y:usize) -> Option<u32> {
+		if x < self.width && y < self.height {
+			self.data.get(y * self.width + x).copied()
+		} else {
+			None
+		}
+	}
 
+	/// Create a new image from a sub-rectangle of this image.
+	pub fn crop(&self, x:usize, y:usize, width:usize, height:usize) -> Result<WindowImage, WindowError> {
+		if width == 0 || height == 0 {
+			return Err(WindowError::InvalidBounds(format!("Crop size {width}x{height} is empty.")));
+		}
+		if x.checked_add(width).is_none_or(|end| end > self.width) || y.checked_add(height).is_none_or(|end| end > self.height) {
+			return Err(WindowError::InvalidBounds(format!("Crop bounds [{x}, {y}, {width}, {height}] exceed image size {}x{}.", self.width, self.height)));
+		}
+		Ok(WindowImage {
+			data: (y..y + height).flat_map(|row| &self.data[row * self.width + x..row * self.width + x + width]).copied().collect(),
+			width,
+			height
+		})
+	}
 
-impl WindowController {
+	/// Find the first pixel, in row-major order, of which all RGB channels are within the given tolerance of the given 0xRRGGBB color. Returns the (x, y) position of the pixel.
+	pub fn find_color(&self, color:u32, tolerance:u8) -> Option<(usize, usize)> {
+		let [_, target_r, target_g, target_b] = color.to_be_bytes();
+		self.data.iter().position(|pixel| {
+			let [_, r, g, b] = pixel.to_be_bytes();
+			r.abs_diff(target_r) <= tolerance && g.abs_diff(target_g) <= tolerance && b.abs_diff(target_b) <= tolerance
+		}).map(|index| (index % self.width, index / self.width))
+	}
 
-	/// Tries to create an image of the inner window. Returns a list of u32 0xAARRGGBB values.
-	pub fn create_window_image(&self) -> Result<WindowImage, Box<dyn Error>> {
-		let window_position:[i32; 4] = self.position();
-		self.create_window_image_with_bounds([0, 0, window_position[2], window_position[3]])
+	/// Get the average color of all pixels that are not fully transparent, as 0xFFRRGGBB. Returns opaque black if there are no such pixels.
+	pub fn average_color(&self) -> u32 {
+		let mut channel_sums:[u64; 3] = [0; 3];
+		let mut pixel_count:u64 = 0;
+		for pixel in self.data.iter().filter(|pixel| *pixel >> 24 != 0) {
+			let [_, r, g, b] = pixel.to_be_bytes();
+			channel_sums[0] += r as u64;
+			channel_sums[1] += g as u64;
+			channel_sums[2] += b as u64;
+			pixel_count += 1;
+		}
+		if pixel_count == 0 {
+			return 0xFF000000;
+		}
+		u32::from_be_bytes([0xFF, (channel_sums[0] / pixel_count) as u8, (channel_sums[1] / pixel_count) as u8, (channel_sums[2] / pixel_count) as u8])
 	}
-	
-	/// Tries to create an image of a subsection of the inner window. Returns a list of u32 0xAARRGGBB values.
-	pub fn create_window_image_with_bounds(&self, bounds:[i32; 4]) -> Result<WindowImage, Box<dyn Error>> {
+
+	/// Get the fraction of pixels that differ from the other image. 0.0 means identical, 1.0 means every pixel differs.
+	pub fn diff(&self, other:&WindowImage) -> Result<f64, WindowError> {
+		if self.width != other.width || self.height != other.height {
+			return Err(WindowError::InvalidBounds(format!("Cannot compare image of size {}x{} to image of size {}x{}.", self.width, self.height, other.width, other.height)));
+		}
+		if self.data.is_empty() {
+			return Ok(0.0);
+		}
+		let different_pixel_count:usize = self.data.iter().zip(&other.data).filter(|(left, right)| left != right).count();
+		Ok(different_pixel_count as f64 / self.data.len() as f64)
+	}
+
+	/// Check if more than the given fraction of pixels changed since the other image. Images of different sizes always count as changed.
+	pub fn changed_since(&self, other:&WindowImage, threshold:f64) -> bool {
+		self.diff(other).map(|difference| difference > threshold).unwrap_or(true)
+	}
+
+	/// Check if nearly all pixels in the image are black, which is what PrintWindow produces for many hardware-accelerated windows.
+	fn is_mostly_black(&self) -> bool {
+		let black_pixel_count:usize = self.data.iter().filter(|pixel| *pixel & 0x00FFFFFF == 0).count();
+		black_pixel_count as f64 >= self.data.len() as f64 * BLACK_FRAME_THRESHOLD
+	}
+
+	/// Return the data of the image as tightly-packed RGBA bytes.
+	pub fn to_rgba8(&self) -> Vec<u8> {
+		self.data.iter().flat_map(|pixel| { let [a, r, g, b] = pixel.to_be_bytes(); [r, g, b, a] }).collect()
+	}
+
+	/// Return the data of the image as tightly-packed BGRA bytes.
+	pub fn to_bgra8(&self) -> Vec<u8> {
+		self.data.iter().flat_map(|pixel| pixel.to_le_bytes()).collect()
+	}
+
+	/// Convert the image to an `image` crate image. Returns None if the data does not match the image size.
+	#[cfg(feature = "image")]
+	pub fn to_dynamic_image(&self) -> Option<image::DynamicImage> {
+		image::RgbaImage::from_raw(self.width as u32, self.height as u32, self.to_rgba8()).map(image::DynamicImage::ImageRgba8)
+	}
+
+	/// Save the image as a 32-bit BMP file.
+	pub fn save_bmp(&self, path:&Path) -> Result<(), WindowError> {
+		const FILE_HEADER_SIZE:u32 = 14;
+
+		let dib:Vec<u8> = self.to_dib();
+		let mut bytes:Vec<u8> = Vec::with_capacity(FILE_HEADER_SIZE as usize + dib.len());
+
+		// BITMAPFILEHEADER.
+		bytes.extend_from_slice(b"BM");
+		bytes.extend_from_slice(&(FILE_HEADER_SIZE + dib.len() as u32).to_le_bytes());
+		bytes.extend_from_slice(&[0u8; 4]);
+		bytes.extend_from_slice(&(FILE_HEADER_SIZE + DIB_HEADER_SIZE).to_le_bytes());
+
+		// Header and pixel data.
+		bytes.extend_from_slice(&dib);
+
+		std::fs::write(path, bytes)?;
+		Ok(())
+	}
+
+	/// Copy the image to the clipboard as a device-independent bitmap.
+	pub fn copy_to_clipboard(&self) -> Result<(), WindowError> {
+		use winapi::{ shared::{ minwindef::HGLOBAL, windef::HWND }, um::{ winbase::{ GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE }, winuser::{ CloseClipboard, CreateWindowExW, DestroyWindow, EmptyClipboard, OpenClipboard, SetClipboardData, CF_DIB, HWND_MESSAGE } } };
+		use std::ptr;
+
+		let dib:Vec<u8> = self.to_dib();
 		unsafe {
 
-			// Validate bounds width/height.
-			if bounds[2] <= 0 || bounds[3] <= 0 {
-				return Err("Invalid bounds size".into());
+			// Copy the bitmap into global memory.
+			let memory:HGLOBAL = GlobalAlloc(GMEM_MOVEABLE, dib.len());
+			if memory.is_null() {
+				return Err(WindowError::ClipboardFailed("Could not allocate global memory.".to_string()));
 			}
-
-			// Determine window/client/padding.
-			let mut window_bounds:RECT = mem::zeroed();
-			let mut window_client_bounds:RECT = mem::zeroed();
-			let mut window_topleft:POINT = POINT { x: 0, y: 0 };
-			GetWindowRect(self.hwnd(), &mut window_bounds);
-			GetClientRect(self.hwnd(), &mut window_client_bounds);
-			ClientToScreen(self.hwnd(), &mut window_topleft);
-			let padding:[i32; 4] = [
-				(window_topleft.x - window_bounds.left).max(0),
-				(window_topleft.y - window_bounds.top).max(0),
-				(window_bounds.right - (window_topleft.x + (window_client_bounds.right - window_client_bounds.left))).max(0),
-				(window_bounds.bottom - (window_topleft.y + (window_client_bounds.bottom - window_client_bounds.top))).max(0)
-			];
-
-			// Calculate padded width. Contains the requested bounds inside the client area. Bitmap must be large enough to include left and top padding.
-			let padded_size:[i32; 2] = [bounds[0] + bounds[2] + padding[0] + padding[2], bounds[1] + bounds[3] + padding[1] + padding[3]];
-			if padded_size[0] <= 0 || padded_size[1] <= 0 {
-				return Err("Computed padded size is invalid".into());
+			let memory_pointer:*mut u8 = GlobalLock(memory) as *mut u8;
+			if memory_pointer.is_null() {
+				GlobalFree(memory);
+				return Err(WindowError::ClipboardFailed("Could not lock global memory.".to_string()));
 			}
+			ptr::copy_nonoverlapping(dib.as_ptr(), memory_pointer, dib.len());
+			GlobalUnlock(memory);
 
-			// Create a device context for the window.
-			let dc:*mut HDC__ = GetDC(self.hwnd());
-			if dc.is_null() {
-				return Err("Could not create device context".into());
+			// Create a message-only window to own the clipboard.
+			let class_name:Vec<u16> = "STATIC".encode_utf16().chain(Some(0)).collect();
+			let owner:HWND = CreateWindowExW(0, class_name.as_ptr(), ptr::null(), 0, 0, 0, 0, 0, HWND_MESSAGE, ptr::null_mut(), ptr::null_mut(), ptr::null_mut());
+			if owner.is_null() {
+				GlobalFree(memory);
+				return Err(WindowError::ClipboardFailed("Could not create clipboard owner window.".to_string()));
 			}
 
-			// Create compatible device context.
-			let hdc:*mut HDC__ = CreateCompatibleDC(dc);
-			if hdc.is_null() {
-				ReleaseDC(self.hwnd(), dc);
-				return Err("Could not create compatible device context.".into())
+			// Hand the memory over to the clipboard. The clipboard owns it once set.
+			if OpenClipboard(owner) == 0 {
+				DestroyWindow(owner);
+				GlobalFree(memory);
+				return Err(WindowError::ClipboardFailed("Could not open clipboard.".to_string()));
 			}
-
-			// Create a compatible bitmap sized to the padded size.
-			let hbitmap:*mut HBITMAP__ = CreateCompatibleBitmap(dc, padded_size[0], padded_size[1]);
-			if hbitmap.is_null() {
-				DeleteDC(hdc);
-				ReleaseDC(self.hwnd(), dc);
-				return Err("Could not create compatible bitmap.".into())
+			EmptyClipboard();
+			let result:HGLOBAL = SetClipboardData(CF_DIB, memory);
+			CloseClipboard();
+			DestroyWindow(owner);
+			if result.is_null() {
+				GlobalFree(memory);
+				return Err(WindowError::ClipboardFailed("Could not set clipboard data.".to_string()));
 			}
+		}
+		Ok(())
+	}
 
-			// Select the bitmap into the DC.
-			let hold:*mut c_void = SelectObject(hdc, hbitmap as *mut _);
-			if hold.is_null() {
-				DeleteObject(hbitmap as *mut _);
-				DeleteDC(hdc);
-				ReleaseDC(self.hwnd(), dc);
-				return Err("Could not select the bitmap in the device context.".into())
-			}
+	/// Return the image as a packed device-independent bitmap. A BITMAPINFOHEADER followed by bottom-up BGRA rows.
+	fn to_dib(&self) -> Vec<u8> {
+		let image_size:u32 = (self.width * self.height * 4) as u32;
+		let mut bytes:Vec<u8> = Vec::with_capacity((DIB_HEADER_SIZE + image_size) as usize);
 
-			// Capture image from window to hdc. Renders the full window. Bitmap was sized to include the non-client areas.
-			let result:i32 = PrintWindow(self.hwnd(), hdc, PW_RENDERFULLCONTENT);
-			if result == 0 {
-				SelectObject(hdc, hold);
-				DeleteObject(hbitmap as *mut _);
-				DeleteDC(hdc);
-				ReleaseDC(self.hwnd(), dc);
-				return Err("PrintWindow failed".into());
-			}
+		// BITMAPINFOHEADER.
+		bytes.extend_from_slice(&DIB_HEADER_SIZE.to_le_bytes());
+		bytes.extend_from_slice(&(self.width as i32).to_le_bytes());
+		bytes.extend_from_slice(&(self.height as i32).to_le_bytes()); // Positive for bottom-up rows.
+		bytes.extend_from_slice(&1u16.to_le_bytes());
+		bytes.extend_from_slice(&32u16.to_le_bytes());
+		bytes.extend_from_slice(&0u32.to_le_bytes()); // BI_RGB.
+		bytes.extend_from_slice(&image_size.to_le_bytes());
+		bytes.extend_from_slice(&[0u8; 16]);
 
-			// Prepare BITMAPINFO for the padded size (top-down)
-			let mut bitmap_info:BITMAPINFO = mem::zeroed();
-			bitmap_info.bmiHeader.biSize = mem::size_of::<BITMAPINFOHEADER>() as DWORD;
-			bitmap_info.bmiHeader.biWidth = padded_size[0];
-			bitmap_info.bmiHeader.biHeight = -padded_size[1]; // Negative to get data top-down.
-			bitmap_info.bmiHeader.biPlanes = 1;
-			bitmap_info.bmiHeader.biBitCount = 32;
-			bitmap_info.bmiHeader.biCompression = BI_RGB;
-
-			// Allocate buffer for the full padded capture (BGRA)
-			let mut bits:Vec<u8> = vec![0u8; (padded_size[0] * padded_size[1] * 4) as usize];
-			let res:i32 = GetDIBits(hdc, hbitmap, 0, padded_size[1] as u32, bits.as_mut_ptr() as *mut c_void, &mut bitmap_info, DIB_RGB_COLORS);
-			if res == 0 {
-				SelectObject(hdc, hold);
-				DeleteObject(hbitmap as *mut _);
-				DeleteDC(hdc);
-				ReleaseDC(self.hwnd(), dc);
-				return Err("GetDIBits failed.".into());
+		// Pixel data, rows bottom-up. Little-endian 0xAARRGGBB is BGRA in memory.
+		for row in self.data.chunks(self.width.max(1)).rev() {
+			for pixel in row {
+				bytes.extend_from_slice(&pixel.to_le_bytes());
 			}
+		}
+		bytes
+	}
 
+	/// Save the image as a PNG file.
+	#[cfg(feature = "png")]
+	pub fn save_png(&self, path:&Path) -> Result<(), WindowError> {
+		use std::{ fs::File, io::BufWriter };
 
-			// Collect data from image, skipping padding.
-			let mut pixels:Vec<u32> = vec![0x00000000; (bounds[2] * bounds[3]) as usize];
-			for output_y in 0..bounds[3] {
-				for output_x in 0..bounds[2] {
-					let (input_x, input_y) = (bounds[0] + padding[0] + output_x, bounds[1] + padding[1] + output_y);
-					let output_index:usize = (output_y * bounds[2] + output_x) as usize;
-					let input_index:usize = (input_y * padded_size[0] + input_x) as usize;
-					pixels[output_index] = u32::from_be_bytes([0xFF, bits[input_index * 4 + 2], bits[input_index * 4 + 1], bits[input_index * 4]]);
-				}
-			}
+		let mut encoder:png::Encoder<BufWriter<File>> = png::Encoder::new(BufWriter::new(File::create(path)?), self.width as u32, self.height as u32);
+		encoder.set_color(png::ColorType::Rgba);
+		encoder.set_depth(png::BitDepth::Eight);
+		encoder.write_header().and_then(|mut writer| writer.write_image_data(&self.to_rgba8())).map_err(|error| WindowError::ImageEncodingFailed(error.to_string()))
+	}
+}
+
+
+
+impl WindowController {
+
+	/// Tries to create an image of the inner window. Returns a list of u32 0xAARRGGBB values.
+	pub fn create_window_image(&self) -> Result<WindowImage, WindowError> {
+		self.capture_session()?.capture()
+	}
+	
+	/// Tries to create an image of a subsection of the inner window. Returns a list of u32 0xAARRGGBB values.
+	pub fn create_window_image_with_bounds<T:Into<Rect>>(&self, bounds:T) -> Result<WindowImage, WindowError> {
+		self.capture_session()?.capture_with_bounds(bounds)
+	}
 
+	/// Tries to create an image of the inner window with the mouse cursor drawn onto it. Returns a list of u32 0xAARRGGBB values.
+	pub fn create_window_image_with_cursor(&self) -> Result<WindowImage, WindowError> {
+		self.capture_session()?.set_include_cursor(true).capture()
+	}
 
-			// Cleanup.
-			SelectObject(hdc, hold);
-			DeleteObject(hbitmap as *mut _);
-			DeleteDC(hdc);
-			ReleaseDC(self.hwnd(), dc);
+	/// Tries to create an image of the inner window, keeping the alpha channel of layered windows. Opaque windows are captured as usual. Returns a list of u32 0xAARRGGBB values.
+	pub fn create_window_image_with_alpha(&self) -> Result<WindowImage, WindowError> {
+		self.capture_session()?.set_preserve_alpha(true).capture()
+	}
 
-			// Return image.
-			Ok(WindowImage {
-				data: pixels,
-				width: bounds[2] as usize,
-				height: bounds[3] as usize
-			})
+	/// Tries to create an image of the inner window. If the regular capture comes back black, as it does for many GPU-accelerated windows, falls back to copying the window area from the screen.
+	pub fn create_window_image_gpu(&self) -> Result<WindowImage, WindowError> {
+		let mut session:CaptureSession = self.capture_session()?;
+		match session.capture() {
+			Ok(image) if !image.is_mostly_black() => Ok(image),
+			_ => session.capture_screen()
 		}
 	}
-}
\ No newline at end of file
+
+	/// Capture the inner window and save it as a PNG file.
+	#[cfg(feature = "png")]
+	pub fn screenshot_to_png(&self, path:&Path) -> Result<(), WindowError> {
+		self.create_window_image()?.save_png(path)
+	}
+}
+
+
+
+/// Convert a 0xAARRGGBB pixel with premultiplied alpha to straight alpha.
+pub(crate) fn unpremultiply(pixel:u32) -> u32 {
+	let [alpha, red, green, blue] = pixel.to_be_bytes();
+	if alpha == 0 || alpha == 0xFF {
+		return pixel;
+	}
+	let unpremultiply_channel = |channel:u8| ((channel as u32 * 0xFF + alpha as u32 / 2) / alpha as u32).min(0xFF) as u8;
+	u32::from_be_bytes([alpha, unpremultiply_channel(red), unpremultiply_channel(green), unpremultiply_channel(blue)])
+}
+
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_image(width:usize, height:usize, data:Vec<u32>) -> WindowImage {
+		WindowImage { data, width, height }
+	}
+
+	fn read_u32(bytes:&[u8], offset:usize) -> u32 {
+		u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+	}
+
+	#[test]
+	fn dib_has_valid_header() {
+		let dib:Vec<u8> = create_image(2, 3, vec![0xFF000000; 6]).to_dib();
+		assert_eq!(dib.len(), 40 + 2 * 3 * 4);
+		assert_eq!(read_u32(&dib, 0), DIB_HEADER_SIZE);
+		assert_eq!(read_u32(&dib, 4) as i32, 2);
+		assert_eq!(read_u32(&dib, 8) as i32, 3);
+		assert_eq!(u16::from_le_bytes([dib[12], dib[13]]), 1);
+		assert_eq!(u16::from_le_bytes([dib[14], dib[15]]), 32);
+		assert_eq!(read_u32(&dib, 16), 0);
+		assert_eq!(read_u32(&dib, 20), 2 * 3 * 4);
+	}
+
+	#[test]
+	fn dib_rows_are_bottom_up() {
+		let dib:Vec<u8> = create_image(1, 2, vec![0xFF112233, 0xFF445566]).to_dib();
+		assert_eq!(&dib[40..44], &[0x66, 0x55, 0x44, 0xFF]);
+		assert_eq!(&dib[44..48], &[0x33, 0x22, 0x11, 0xFF]);
+	}
+
+	#[test]
+	fn bmp_file_has_valid_header() {
+		let path:std::path::PathBuf = std::env::temp_dir().join(format!("window_controller_test_{}.bmp", std::process::id()));
+		create_image(2, 2, vec![0xFF000000; 4]).save_bmp(&path).unwrap();
+		let bytes:Vec<u8> = std::fs::read(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(&bytes[0..2], b"BM");
+		assert_eq!(read_u32(&bytes, 2) as usize, bytes.len());
+		assert_eq!(read_u32(&bytes, 10), 14 + DIB_HEADER_SIZE);
+	}
+
+	#[test]
+	fn diff_rejects_size_mismatch() {
+		let left:WindowImage = create_image(2, 1, vec![0; 2]);
+		let right:WindowImage = create_image(1, 2, vec![0; 2]);
+		assert!(left.diff(&right).is_err());
+		assert!(left.changed_since(&right, 1.0));
+	}
+
+	#[test]
+	fn diff_counts_changed_pixels() {
+		let left:WindowImage = create_image(2, 2, vec![0xFF000000, 0xFF000000, 0xFF000000, 0xFF000000]);
+		let right:WindowImage = create_image(2, 2, vec![0xFF000000, 0xFFFFFFFF, 0xFF000000, 0xFFFFFFFF]);
+		assert_eq!(left.diff(&right).unwrap(), 0.5);
+		assert_eq!(left.diff(&left).unwrap(), 0.0);
+	}
+
+	#[test]
+	fn average_color_skips_transparent_pixels() {
+		let image:WindowImage = create_image(3, 1, vec![0xFF204060, 0xFF406080, 0x00FFFFFF]);
+		assert_eq!(image.average_color(), 0xFF305070);
+		assert_eq!(create_image(1, 1, vec![0x00FFFFFF]).average_color(), 0xFF000000);
+	}
+
+	#[test]
+	fn find_color_returns_first_row_major_match() {
+		let image:WindowImage = create_image(3, 2, vec![0xFF000000, 0xFF000000, 0xFFFF0000, 0xFFFF0000, 0xFF000000, 0xFF000000]);
+		assert_eq!(image.find_color(0xFF0000, 0), Some((2, 0)));
+		assert_eq!(image.find_color(0xF00000, 0x10), Some((2, 0)));
+		assert_eq!(image.find_color(0x00FF00, 0x10), None);
+	}
+
+	#[test]
+	fn unpremultiply_keeps_edge_alpha_values() {
+		assert_eq!(unpremultiply(0x00123456), 0x00123456);
+		assert_eq!(unpremultiply(0xFF123456), 0xFF123456);
+	}
+
+	#[test]
+	fn unpremultiply_rounds_and_clamps() {
+		assert_eq!(unpremultiply(0x80404040), 0x80808080);
+		assert_eq!(unpremultiply(0x80808080), 0x80FFFFFF);
+		assert_eq!(unpremultiply(0x80FF0000), 0x80FF0000);
+	}
+}
@@ -0,0 +1,94 @@
+use winapi::um::winuser::{ INPUT, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEINPUT, SendInput, SetCursorPos };
+use std::mem;
+use crate::WindowController;
+
+
+
+impl WindowController {
+
+	/* KEYBOARD METHODS */
+
+	/// Activate the window and type the given text into it.
+	pub fn type_text(&self, text:&str) {
+		self.activate();
+
+		// Characters outside the BMP are encoded as surrogate pairs, which the receiving window combines back into a single character.
+		let inputs:Vec<INPUT> = text.encode_utf16().flat_map(|code_unit| [
+			WindowController::keyboard_input(0, code_unit, KEYEVENTF_UNICODE),
+			WindowController::keyboard_input(0, code_unit, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP)
+		]).collect();
+		WindowController::send_inputs(inputs);
+	}
+
+	/// Activate the window and press the given virtual key while holding the given modifier keys.
+	pub fn send_key(&self, vk:u16, modifiers:&[u16]) {
+		self.activate();
+
+		let mut inputs:Vec<INPUT> = Vec::with_capacity(modifiers.len() * 2 + 2);
+		inputs.extend(modifiers.iter().map(|modifier| WindowController::keyboard_input(*modifier, 0, 0)));
+		inputs.push(WindowController::keyboard_input(vk, 0, 0));
+		inputs.push(WindowController::keyboard_input(vk, 0, KEYEVENTF_KEYUP));
+		inputs.extend(modifiers.iter().rev().map(|modifier| WindowController::keyboard_input(*modifier, 0, KEYEVENTF_KEYUP)));
+		WindowController::send_inputs(inputs);
+	}
+
+
+
+	/* MOUSE METHODS */
+
+	/// Left-click at the given position relative to the client area of the window.
+	pub fn click_at(&self, x:i32, y:i32, activate:bool) {
+		self.mouse_action_at(x, y, activate, &[MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP]);
+	}
+
+	/// Double-click at the given position relative to the client area of the window.
+	pub fn double_click_at(&self, x:i32, y:i32, activate:bool) {
+		self.mouse_action_at(x, y, activate, &[MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP]);
+	}
+
+	/// Right-click at the given position relative to the client area of the window.
+	pub fn right_click_at(&self, x:i32, y:i32, activate:bool) {
+		self.mouse_action_at(x, y, activate, &[MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP]);
+	}
+
+	/// Move the cursor to the given position relative to the client area of the window and send the given mouse events.
+	fn mouse_action_at(&self, x:i32, y:i32, activate:bool, events:&[u32]) {
+		if activate {
+			self.activate();
+		}
+		let client_rect:[i32; 4] = self.client_rect();
+		unsafe { SetCursorPos(client_rect[0] + x, client_rect[1] + y); }
+		WindowController::send_inputs(events.iter().map(|event| WindowController::mouse_input(*event)).collect());
+	}
+
+
+
+	/* HELPER METHODS */
+
+	/// Create a keyboard input event.
+	fn keyboard_input(vk:u16, scan:u16, flags:u32) -> INPUT {
+		unsafe {
+			let mut input:INPUT = mem::zeroed();
+			input.type_ = INPUT_KEYBOARD;
+			*input.u.ki_mut() = KEYBDINPUT { wVk: vk, wScan: scan, dwFlags: flags, time: 0, dwExtraInfo: 0 };
+			input
+		}
+	}
+
+	/// Create a mouse input event at the current cursor position.
+	fn mouse_input(flags:u32) -> INPUT {
+		unsafe {
+			let mut input:INPUT = mem::zeroed();
+			input.type_ = INPUT_MOUSE;
+			*input.u.mi_mut() = MOUSEINPUT { dx: 0, dy: 0, mouseData: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 };
+			input
+		}
+	}
+
+	/// Send a list of input events.
+	fn send_inputs(mut inputs:Vec<INPUT>) {
+		if !inputs.is_empty() {
+			unsafe { SendInput(inputs.len() as u32, inputs.as_mut_ptr(), mem::size_of::<INPUT>() as i32); }
+		}
+	}
+}
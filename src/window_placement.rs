@@ -0,0 +1,70 @@
+use winapi::{ shared::windef::{ POINT, RECT }, um::winuser::{ GetWindowPlacement, SetWindowPlacement, WINDOWPLACEMENT } };
+use std::mem;
+use crate::WindowController;
+
+
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowPlacement {
+	pub show_command:u32, // SW_* value.
+	pub flags:u32, // WPF_* flags.
+	pub min_position:[i32; 2],
+	pub max_position:[i32; 2],
+	pub restore_rect:[i32; 4] // xywh in workspace coordinates, the position the window has when restored.
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WindowState {
+	Hidden,
+	Minimized,
+	Maximized,
+	Normal
+}
+
+
+
+impl WindowController {
+
+	/// Get the show state of the window. Hidden windows are reported as hidden regardless of their placement.
+	pub fn state(&self) -> WindowState {
+		use winapi::um::winuser::{ SW_MINIMIZE, SW_SHOWMAXIMIZED, SW_SHOWMINIMIZED, SW_SHOWMINNOACTIVE };
+
+		if !self.is_visible() {
+			return WindowState::Hidden;
+		}
+		match self.placement().show_command as i32 {
+			SW_SHOWMINIMIZED | SW_MINIMIZE | SW_SHOWMINNOACTIVE => WindowState::Minimized,
+			SW_SHOWMAXIMIZED => WindowState::Maximized,
+			_ => WindowState::Normal
+		}
+	}
+
+	/// Get the placement of the window, including its show state and the position it has when restored.
+	pub fn placement(&self) -> WindowPlacement {
+		unsafe {
+			let mut placement:WINDOWPLACEMENT = mem::zeroed();
+			placement.length = mem::size_of::<WINDOWPLACEMENT>() as u32;
+			GetWindowPlacement(self.hwnd(), &mut placement);
+			WindowPlacement {
+				show_command: placement.showCmd,
+				flags: placement.flags,
+				min_position: [placement.ptMinPosition.x, placement.ptMinPosition.y],
+				max_position: [placement.ptMaxPosition.x, placement.ptMaxPosition.y],
+				restore_rect: [placement.rcNormalPosition.left, placement.rcNormalPosition.top, placement.rcNormalPosition.right - placement.rcNormalPosition.left, placement.rcNormalPosition.bottom - placement.rcNormalPosition.top]
+			}
+		}
+	}
+
+	/// Set the placement of the window, including its show state and the position it has when restored.
+	pub fn set_placement(&self, placement:&WindowPlacement) {
+		let placement:WINDOWPLACEMENT = WINDOWPLACEMENT {
+			length: mem::size_of::<WINDOWPLACEMENT>() as u32,
+			flags: placement.flags,
+			showCmd: placement.show_command,
+			ptMinPosition: POINT { x: placement.min_position[0], y: placement.min_position[1] },
+			ptMaxPosition: POINT { x: placement.max_position[0], y: placement.max_position[1] },
+			rcNormalPosition: RECT { left: placement.restore_rect[0], top: placement.restore_rect[1], right: placement.restore_rect[0] + placement.restore_rect[2], bottom: placement.restore_rect[1] + placement.restore_rect[3] }
+		};
+		unsafe { SetWindowPlacement(self.hwnd(), &placement); }
+	}
+}
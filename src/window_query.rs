@@ -0,0 +1,72 @@
+use crate::WindowController;
+
+
+
+#[derive(Clone, Default)]
+pub struct WindowQuery {
+	title_contains:Option<String>,
+	class:Option<String>,
+	visible:Option<bool>,
+	pid:Option<u32>
+}
+impl WindowQuery {
+
+	/* CONSTRUCTOR METHODS */
+
+	/// Create a new query matching all windows.
+	pub fn new() -> WindowQuery {
+		WindowQuery::default()
+	}
+
+
+
+	/* BUILDER METHODS */
+
+	/// Only match windows with a title containing the given string.
+	pub fn title_contains(mut self, title:&str) -> Self {
+		self.title_contains = Some(title.to_string());
+		self
+	}
+
+	/// Only match windows with the given class name.
+	pub fn class(mut self, class:&str) -> Self {
+		self.class = Some(class.to_string());
+		self
+	}
+
+	/// Only match windows with the given visibility.
+	pub fn visible(mut self, visible:bool) -> Self {
+		self.visible = Some(visible);
+		self
+	}
+
+	/// Only match windows belonging to the process with the given ID. None removes the criterion.
+	pub fn pid(mut self, pid:Option<u32>) -> Self {
+		self.pid = pid;
+		self
+	}
+
+
+
+	/* USAGE METHODS */
+
+	/// Check if the given window matches all criteria of the query.
+	pub fn matches(&self, window:&WindowController) -> bool {
+		self.pid.is_none_or(|pid| window.pid() == pid) &&
+		self.visible.is_none_or(|visible| window.is_visible() == visible) &&
+		self.class.as_ref().is_none_or(|class| window.class_name() == *class) &&
+		self.title_contains.as_ref().is_none_or(|title| window.title().contains(title))
+	}
+
+	/// Try to find one window matching the query.
+	pub fn find_one(&self) -> Option<WindowController> {
+		let query:WindowQuery = self.clone();
+		WindowController::find_one(move |window| query.matches(window))
+	}
+
+	/// Find all windows matching the query.
+	pub fn find_all(&self) -> Vec<WindowController> {
+		let query:WindowQuery = self.clone();
+		WindowController::find_all(move |window| query.matches(window))
+	}
+}
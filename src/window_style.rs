@@ -1,4 +1,4 @@
-use crate::WindowController;
+use crate::{ Rect, WindowController };
 
 
 
@@ -7,25 +7,31 @@ pub struct WindowStyle {
 	style_flags:u32,
 	extended_style_flags:u32,
 	always_on_top:bool,
-	target_position:Option<[i32; 4]>
+	target_position:Option<[i32; 4]>,
+	transcolor:Option<u32>,
+	opacity:Option<u8>
 }
 impl WindowStyle {
 
 	/* CONSTRUCTOR METHODS */
 
-	/// Create a new style.
+	/// Create a new style. The trans-color, opacity and always-on-top state already set on the window are kept.
 	pub fn new(window:WindowController) -> WindowStyle {
-		use winapi::um::winuser::{ GetWindowLongPtrW, GWL_STYLE, GWL_EXSTYLE };
+		use winapi::um::winuser::{ GetWindowLongPtrW, GWL_STYLE, GWL_EXSTYLE, WS_EX_TOPMOST };
 		use winapi::shared::windef::HWND__;
 
 		let hwnd:*mut HWND__ = window.hwnd();
+		let (transcolor, opacity) = window.layered_attributes();
 		unsafe { 
+			let extended_style_flags:u32 = GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32;
 			WindowStyle {
 				window,
 				style_flags: GetWindowLongPtrW(hwnd, GWL_STYLE) as u32,
-				extended_style_flags: GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32,
-				always_on_top: false,
-				target_position: None
+				extended_style_flags,
+				always_on_top: extended_style_flags & WS_EX_TOPMOST != 0,
+				target_position: None,
+				transcolor,
+				opacity
 			}
 		}
 	}
@@ -55,36 +61,90 @@ impl WindowStyle {
 			SetWindowLongPtrW(self.window.hwnd(), GWL_EXSTYLE, self.extended_style_flags as isize);
 
 			let (position, u_flags) = match self.target_position {
-				Some(target_position) => (target_position, 0),
+				Some(target_position) => (target_position, SWP_FRAMECHANGED),
 				None => ([0; 4], SWP_NOMOVE | SWP_NOSIZE | SWP_FRAMECHANGED | SWP_NOACTIVATE)
 			};
-			SetWindowPos(self.window.hwnd(), if self.always_on_top { HWND_TOPMOST } else { HWND_NOTOPMOST }, position[0], position[1], position[0] + position[2], position[1] + position[3], u_flags);
+			SetWindowPos(self.window.hwnd(), if self.always_on_top { HWND_TOPMOST } else { HWND_NOTOPMOST }, position[0], position[1], position[2], position[3], u_flags);
 		}
 	}
 
 
 
+	/* PROPERTY GETTER METHODS */
+
+	/// Get the current styling flags.
+	pub fn style_flags(&self) -> u32 {
+		self.style_flags
+	}
+
+	/// Get the current extended styling flags.
+	pub fn ext_style_flags(&self) -> u32 {
+		self.extended_style_flags
+	}
+
+	/// Check if all of the given styling flags are set.
+	pub fn has_style(&self, flag:u32) -> bool {
+		self.style_flags & flag == flag
+	}
+
+
+
 	/* SPECIFIC USAGE METHODS */
 
-	/// Set a trans-color to the window.
+	/// Set a trans-color to the window. The trans-color has no effect until the window has the layered style, so the extended style is applied right away instead of waiting for `apply`.
 	pub fn set_transcolor(&mut self, color:u32) -> &mut Self {
-		use winapi::um::winuser::{ GetDC, GetWindowLongPtrW, SetLayeredWindowAttributes, ReleaseDC, GWL_EXSTYLE, WS_EX_LAYERED, WS_EX_TRANSPARENT, LWA_COLORKEY };
-		use winapi::shared::windef::HDC__;
+		use winapi::um::winuser::{ WS_EX_LAYERED, WS_EX_TRANSPARENT };
 
-		unsafe {
-			let color_bbggrr:u32 = (color & 0xFF000000) | ((color & 0xFF) << 16) | (((color >> 8) & 0xFF) << 8) | ((color >> 16) & 0xFF);
+		self.transcolor = Some(color);
+		self.set_style(0, WS_EX_LAYERED | WS_EX_TRANSPARENT);
+		self.apply_extended_style();
+		self.update_layered_attributes();
+		self
+	}
+
+	/// Set the opacity of the window. 255 is fully opaque, 0 is fully transparent. Like the trans-color, applies the layered style right away.
+	pub fn set_opacity(&mut self, alpha:u8) -> &mut Self {
+		use winapi::um::winuser::WS_EX_LAYERED;
+
+		self.opacity = Some(alpha);
+		self.set_style(0, WS_EX_LAYERED);
+		self.apply_extended_style();
+		self.update_layered_attributes();
+		self
+	}
+
+	/// Toggle click-through, letting mouse events pass through to the windows beneath. Keeps the window visible by making sure it has an opacity, an opacity already set on the window is kept.
+	pub fn set_click_through(&mut self, enabled:bool) -> &mut Self {
+		use winapi::um::winuser::{ WS_EX_LAYERED, WS_EX_TRANSPARENT };
 
+		if enabled {
 			self.set_style(0, WS_EX_LAYERED | WS_EX_TRANSPARENT);
-			
-			let device_context:*mut HDC__ = GetDC(self.window.hwnd());
-			GetWindowLongPtrW(self.window.hwnd(), GWL_EXSTYLE);
-			let _success:i32 = SetLayeredWindowAttributes(self.window.hwnd(), color_bbggrr, 0, LWA_COLORKEY);
-			ReleaseDC(self.window.hwnd(), device_context)
-		};
 
+			// The opacity is seeded from the layered attributes of the window, so it is only missing when the window has no LWA_ALPHA yet.
+			if self.opacity.is_none() {
+				self.opacity = Some(0xFF);
+			}
+			self.apply_extended_style();
+			self.update_layered_attributes();
+		} else {
+			self.remove_style(0, WS_EX_TRANSPARENT);
+			self.apply_extended_style();
+		}
 		self
 	}
 
+	/// Apply the extended styling flags immediately. Layered window attributes only work once the window actually has the layered style.
+	fn apply_extended_style(&self) {
+		use winapi::um::winuser::{ SetWindowLongPtrW, GWL_EXSTYLE };
+
+		unsafe { SetWindowLongPtrW(self.window.hwnd(), GWL_EXSTYLE, self.extended_style_flags as isize); }
+	}
+
+	/// Update the layered window attributes to match the trans-color and opacity settings.
+	fn update_layered_attributes(&self) {
+		self.window.set_layered_attributes(self.transcolor, self.opacity);
+	}
+
 	/// Toggle the caption of the window.
 	pub fn set_caption(&mut self, show_caption:bool) -> &mut Self {
 		use winapi::um::winuser::WS_CAPTION;
@@ -104,9 +164,75 @@ impl WindowStyle {
 		self
 	}
 
+	/// Toggle the sizing border of the window.
+	pub fn set_resizable(&mut self, resizable:bool) -> &mut Self {
+		use winapi::um::winuser::WS_THICKFRAME;
+
+		if resizable {
+			self.set_style(WS_THICKFRAME, 0);
+		} else {
+			self.remove_style(WS_THICKFRAME, 0);
+		}
+
+		self
+	}
+
+	/// Toggle the minimize button of the window.
+	pub fn set_minimize_box(&mut self, show_minimize_box:bool) -> &mut Self {
+		use winapi::um::winuser::WS_MINIMIZEBOX;
+
+		if show_minimize_box {
+			self.set_style(WS_MINIMIZEBOX, 0);
+		} else {
+			self.remove_style(WS_MINIMIZEBOX, 0);
+		}
+
+		self
+	}
+
+	/// Toggle the maximize button of the window.
+	pub fn set_maximize_box(&mut self, show_maximize_box:bool) -> &mut Self {
+		use winapi::um::winuser::WS_MAXIMIZEBOX;
+
+		if show_maximize_box {
+			self.set_style(WS_MAXIMIZEBOX, 0);
+		} else {
+			self.remove_style(WS_MAXIMIZEBOX, 0);
+		}
+
+		self
+	}
+
+	/// Toggle all borders of the window, including the caption, sizing border and window buttons. The frame change takes effect when the style is applied.
+	pub fn set_borderless(&mut self, borderless:bool) -> &mut Self {
+		use winapi::um::winuser::{ WS_CAPTION, WS_THICKFRAME, WS_MINIMIZEBOX, WS_MAXIMIZEBOX, WS_SYSMENU };
+
+		let border_flags:u32 = WS_CAPTION | WS_THICKFRAME | WS_MINIMIZEBOX | WS_MAXIMIZEBOX | WS_SYSMENU;
+		if borderless {
+			self.remove_style(border_flags, 0);
+		} else {
+			self.set_style(border_flags, 0);
+		}
+
+		self
+	}
+
+	/// Toggle the tool-window style, which hides the window from the taskbar and Alt-Tab.
+	pub fn set_tool_window(&mut self, tool_window:bool) -> &mut Self {
+		use winapi::um::winuser::WS_EX_TOOLWINDOW;
+
+		if tool_window {
+			self.set_style(0, WS_EX_TOOLWINDOW);
+		} else {
+			self.remove_style(0, WS_EX_TOOLWINDOW);
+		}
+
+		self
+	}
+
 	/// Set the [x, y, w, h] position of the window.
-	pub fn set_position(&mut self, position:[i32; 4]) -> &mut Self {
-		self.target_position = Some(position);
+	pub fn set_position<T:Into<Rect>>(&mut self, position:T) -> &mut Self {
+		self.target_position = Some(position.into().into());
 		self
 	}
 }
@@ -114,4 +240,51 @@ impl Drop for WindowStyle {
 	fn drop(&mut self) {
 		self.apply();
 	}
-}
\ No newline at end of file
+}
+
+
+
+impl WindowController {
+
+	/// Get the 0xRRGGBB trans-color and the opacity of a layered window. Both are None when not set, or when the window is not layered.
+	pub(crate) fn layered_attributes(&self) -> (Option<u32>, Option<u8>) {
+		use winapi::um::winuser::{ GetLayeredWindowAttributes, GetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA, LWA_COLORKEY, WS_EX_LAYERED };
+
+		let mut color_bbggrr:u32 = 0;
+		let mut alpha:u8 = 0xFF;
+		let mut flags:u32 = 0;
+		unsafe {
+			if GetWindowLongPtrW(self.hwnd(), GWL_EXSTYLE) as u32 & WS_EX_LAYERED == 0 || GetLayeredWindowAttributes(self.hwnd(), &mut color_bbggrr, &mut alpha, &mut flags) == 0 {
+				return (None, None);
+			}
+		}
+		(
+			if flags & LWA_COLORKEY != 0 { Some(((color_bbggrr & 0xFF) << 16) | (((color_bbggrr >> 8) & 0xFF) << 8) | ((color_bbggrr >> 16) & 0xFF)) } else { None },
+			if flags & LWA_ALPHA != 0 { Some(alpha) } else { None }
+		)
+	}
+
+	/// Set the 0xRRGGBB trans-color and the opacity of the window, adding the layered style if required. None disables the attribute.
+	pub(crate) fn set_layered_attributes(&self, transcolor:Option<u32>, opacity:Option<u8>) {
+		use winapi::um::winuser::{ GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA, LWA_COLORKEY, WS_EX_LAYERED };
+
+		let mut flags:u32 = 0;
+		let mut color_bbggrr:u32 = 0;
+		let mut alpha:u8 = 0xFF;
+		if let Some(color) = transcolor {
+			color_bbggrr = ((color & 0xFF) << 16) | (((color >> 8) & 0xFF) << 8) | ((color >> 16) & 0xFF);
+			flags |= LWA_COLORKEY;
+		}
+		if let Some(opacity) = opacity {
+			alpha = opacity;
+			flags |= LWA_ALPHA;
+		}
+		unsafe {
+			let extended_style_flags:isize = GetWindowLongPtrW(self.hwnd(), GWL_EXSTYLE);
+			if extended_style_flags & WS_EX_LAYERED as isize == 0 {
+				SetWindowLongPtrW(self.hwnd(), GWL_EXSTYLE, extended_style_flags | WS_EX_LAYERED as isize);
+			}
+			SetLayeredWindowAttributes(self.hwnd(), color_bbggrr, alpha, flags);
+		}
+	}
+}